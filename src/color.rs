@@ -0,0 +1,27 @@
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use termcolor::ColorChoice;
+
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turns off colored output for the rest of the run, as if `NO_COLOR` was set.
+pub fn disable() {
+    DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns whether output may be colored.
+///
+/// Colors are off when `--no-color` is given or the `NO_COLOR` environment variable is set.
+pub fn enabled() -> bool {
+    !DISABLED.load(Ordering::Relaxed) && env::var_os("NO_COLOR").is_none()
+}
+
+/// Returns the [`ColorChoice`] for help and error output.
+pub fn choice() -> ColorChoice {
+    if enabled() {
+        ColorChoice::Auto
+    } else {
+        ColorChoice::Never
+    }
+}
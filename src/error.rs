@@ -1,17 +1,164 @@
-use std::io::Write;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::PathBuf;
 
-use termcolor::{BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
+use termcolor::{BufferWriter, Color, ColorSpec, WriteColor};
+
+use crate::color;
 
 /// A simple [`Result`](std::result::Result) type used in this application.
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = std::result::Result<T, AppError>;
+
+/// The [`Error`](std::error::Error) type used throughtout this application.
+#[derive(Debug)]
+pub enum AppError {
+    /// A path provided by the user does not exist.
+    PathNotFound(PathBuf),
+    /// A path provided by the user is a directory where a file was expected.
+    NotAFile(PathBuf),
+    /// A file provided by the user is not valid UTF-8 text.
+    NotUtf8(PathBuf),
+    /// A word list file could not be parsed.
+    InvalidWordList(PathBuf, serde_json::Error),
+    /// A word list file has no words in it.
+    EmptyWordList(PathBuf),
+    /// Every word in the word list has already been played.
+    AllWordsUsed,
+    /// No directory to store the data file in could be determined.
+    DataDirNotFound,
+    /// An unrecognized command line argument was provided.
+    InvalidArgument(String),
+    /// The data file contains an invalid setting.
+    InvalidConfig(String),
+    /// An IO operation failed, optionally with a description of what was being done.
+    Io {
+        context: Option<String>,
+        source: io::Error,
+    },
+    /// A json value could not be serialized or deserialized.
+    Json(serde_json::Error),
+    /// Another session of the app holds the lock on the file at the given path.
+    SessionLocked(PathBuf),
+    /// The system clipboard could not be accessed.
+    Clipboard(arboard::Error),
+    /// The game can't be shown because the output isn't a terminal that supports it.
+    NoTerminal,
+}
+
+impl AppError {
+    /// Returns the process exit code for this error.
+    ///
+    /// - `1`: generic failure, such as an IO or clipboard error
+    /// - `2`: invalid command line arguments
+    /// - `3`: a word list file is missing or invalid
+    /// - `4`: all available words have been used
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::InvalidArgument(_) => 2,
+            Self::PathNotFound(_)
+            | Self::NotAFile(_)
+            | Self::NotUtf8(_)
+            | Self::InvalidWordList(..)
+            | Self::EmptyWordList(_) => 3,
+            Self::AllWordsUsed => 4,
+            _ => 1,
+        }
+    }
+
+    /// Returns whether the error is from writing to a closed pipe.
+    pub fn is_broken_pipe(&self) -> bool {
+        matches!(self, Self::Io { source, .. } if source.kind() == io::ErrorKind::BrokenPipe)
+    }
+
+    /// Creates an [`AppError::Io`] with a description of the failed operation.
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        Self::Io {
+            context: Some(context.into()),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PathNotFound(path) => write!(f, "path does not exist: {}", path.display()),
+            Self::NotAFile(path) => {
+                write!(
+                    f,
+                    "path is a directory, expected a file: {}",
+                    path.display()
+                )
+            },
+            Self::NotUtf8(path) => write!(f, "file is not valid UTF-8: {}", path.display()),
+            Self::InvalidWordList(path, e) => {
+                write!(f, "invalid word list at {}: {e}", path.display())
+            },
+            Self::EmptyWordList(path) => write!(f, "word list is empty: {}", path.display()),
+            Self::AllWordsUsed => write!(f, "all available words have been used"),
+            Self::DataDirNotFound => write!(f, "unable to find a directory to store data in"),
+            Self::InvalidArgument(arg) => write!(f, "invalid argument: {arg}"),
+            Self::InvalidConfig(msg) => write!(f, "invalid configuration: {msg}"),
+            Self::Io {
+                context: Some(context),
+                source,
+            } => write!(f, "{context}: {source}"),
+            Self::Io {
+                context: None,
+                source,
+            } => write!(f, "{source}"),
+            Self::Json(e) => write!(f, "{e}"),
+            Self::SessionLocked(path) => write!(
+                f,
+                "another wordle-cli session is running (it holds the lock on {})",
+                path.display()
+            ),
+            Self::Clipboard(e) => write!(f, "unable to access clipboard: {e}"),
+            Self::NoTerminal => write!(
+                f,
+                "interactive mode unavailable in this terminal, use --play-from to play without \
+                 it"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidWordList(_, e) | Self::Json(e) => Some(e),
+            Self::Io { source, .. } => Some(source),
+            Self::Clipboard(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(source: io::Error) -> Self {
+        Self::Io {
+            context: None,
+            source,
+        }
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
 
-/// A simple generic [`Error`](std::error::Error) type used throughtout this application.
-pub type Error = Box<dyn std::error::Error + Send + Sync>;
+impl From<arboard::Error> for AppError {
+    fn from(e: arboard::Error) -> Self {
+        Self::Clipboard(e)
+    }
+}
 
-/// Exits the application with an error message and code.
-pub fn exit(err: Error, code: i32) -> ! {
+/// Exits the application with an error message and the error's exit code.
+pub fn exit(err: AppError) -> ! {
     let error = || -> Result<()> {
-        let bufwtr = BufferWriter::stderr(ColorChoice::Auto);
+        let bufwtr = BufferWriter::stderr(color::choice());
         let mut buffer = bufwtr.buffer();
         buffer.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))?;
 
@@ -27,5 +174,5 @@ pub fn exit(err: Error, code: i32) -> ! {
         eprintln!("error: {}", e);
     }
 
-    std::process::exit(code);
+    std::process::exit(err.exit_code());
 }
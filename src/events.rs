@@ -0,0 +1,74 @@
+#[cfg(test)]
+use std::collections::VecDeque;
+use std::io;
+use std::time::Duration;
+
+use crossterm::event::{self, Event};
+#[cfg(test)]
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// A source of terminal events.
+///
+/// The game reads input through this trait instead of calling [`event::read`] directly, so it
+/// can be driven by a scripted sequence of events when needed.
+pub trait EventSource {
+    /// Waits up to the timeout for an event, returning whether one is ready to be read.
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool>;
+
+    /// Returns the next event, waiting for one if there is none yet.
+    fn read(&mut self) -> io::Result<Event>;
+}
+
+/// An [`EventSource`] backed by the terminal.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TerminalEvents;
+
+impl EventSource for TerminalEvents {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        event::poll(timeout)
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        event::read()
+    }
+}
+
+/// An [`EventSource`] that plays back a fixed sequence of events, for driving the game in tests.
+///
+/// Once the events run out it keeps returning *Esc*, which leaves any screen of the game.
+#[cfg(test)]
+#[derive(Clone, Debug, Default)]
+pub struct ScriptedEvents(VecDeque<Event>);
+
+#[cfg(test)]
+impl ScriptedEvents {
+    pub fn new(events: impl IntoIterator<Item = Event>) -> Self {
+        Self(events.into_iter().collect())
+    }
+
+    /// Returns the events of typing each character of the text, then pressing enter.
+    pub fn typed(text: &str) -> Vec<Event> {
+        text.chars()
+            .map(KeyCode::Char)
+            .chain([KeyCode::Enter])
+            .map(key)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl EventSource for ScriptedEvents {
+    fn poll(&mut self, _timeout: Duration) -> io::Result<bool> {
+        Ok(!self.0.is_empty())
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        Ok(self.0.pop_front().unwrap_or_else(|| key(KeyCode::Esc)))
+    }
+}
+
+/// Returns the event of pressing the key.
+#[cfg(test)]
+pub fn key(code: KeyCode) -> Event {
+    Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
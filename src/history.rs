@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, Result};
+use crate::load_file;
+
+/// A finished game, as kept in the history.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Game {
+    /// The UTC date the game was played on, as `YYYY-MM-DD`.
+    pub date: String,
+    /// The 1-based number of the puzzle in the word list.
+    pub puzzle: usize,
+    pub word: String,
+    pub attempts: usize,
+    pub win: bool,
+    pub duration: Duration,
+}
+
+/// Every finished game, oldest first.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct History(Vec<Game>);
+
+impl History {
+    /// Loads the history at the given path.
+    ///
+    /// Returns an empty history if no game has been finished yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        if path.exists() {
+            load_file(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Adds a finished game to the end of the history.
+    pub fn record(&mut self, game: Game) {
+        self.0.push(game);
+    }
+
+    /// Returns the number of games in the history.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Writes the history to a CSV file with a header row, for spreadsheets.
+    pub fn export_csv(&self, path: &Path) -> Result<()> {
+        let mut csv = String::from("date,puzzle,word,attempts,win,duration_secs\n");
+        for game in &self.0 {
+            let fields = [
+                game.date.clone(),
+                game.puzzle.to_string(),
+                game.word.clone(),
+                game.attempts.to_string(),
+                game.win.to_string(),
+                format!("{:.3}", game.duration.as_secs_f64()),
+            ];
+            let fields: Vec<_> = fields.iter().map(|f| csv_field(f)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+
+        fs::write(path, csv)
+            .map_err(|e| AppError::io(format!("failed to write {}", path.display()), e))
+    }
+}
+
+/// Quotes the field if it has a comma, quote or line break, doubling any quotes in it.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_only_fields_that_need_it() {
+        assert_eq!(csv_field("SLATE"), "SLATE");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn exports_a_header_and_a_row_per_game() {
+        let mut history = History::default();
+        history.record(Game {
+            date: "2026-10-16".to_string(),
+            puzzle: 43,
+            word: "SLATE".to_string(),
+            attempts: 2,
+            win: true,
+            duration: Duration::from_millis(75_250),
+        });
+        history.record(Game {
+            date: "2026-10-17".to_string(),
+            puzzle: 44,
+            word: "ODD,WORD".to_string(),
+            attempts: 6,
+            win: false,
+            duration: Duration::from_secs(300),
+        });
+
+        let path =
+            std::env::temp_dir().join(format!("wordle-cli-{}-history.csv", std::process::id()));
+        history.export_csv(&path).unwrap();
+        let csv = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            csv,
+            "date,puzzle,word,attempts,win,duration_secs\n\
+             2026-10-16,43,SLATE,2,true,75.250\n\
+             2026-10-17,44,\"ODD,WORD\",6,false,300.000\n"
+        );
+    }
+}
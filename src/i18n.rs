@@ -0,0 +1,181 @@
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, Result};
+
+/// Locales bundled with the app, by language code.
+const BUNDLED_LOCALES: [(&str, &[u8]); 1] = [("es", include_bytes!("../data/locales/es.json"))];
+
+/// The text shown to the player.
+///
+/// Placeholders in braces, like `{word}`, are replaced when the message is shown. Text between
+/// asterisks in `controls` is shown in bold. Messages missing from a locale file fall back to
+/// English.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Messages {
+    pub about: String,
+    pub usage_heading: String,
+    pub options_heading: String,
+    pub controls: String,
+    pub invalid_guess: String,
+    pub secret_prompt: String,
+    pub secret_title: String,
+    pub did_you_mean: String,
+    pub suggestion: String,
+    pub theme_changed: String,
+    pub gray_hint: String,
+    pub no_hints: String,
+    pub unknown_command: String,
+    pub guesses_title: String,
+    pub alphabet_title: String,
+    pub found_letters: String,
+    pub word_position: String,
+    pub mode_replay: String,
+    pub mode_challenge: String,
+    pub mode_practice_weak: String,
+    pub mode_practice: String,
+    pub mode_adversarial: String,
+    pub mode_two_player: String,
+    pub guesses_left: String,
+    pub too_small: String,
+    pub win: String,
+    /// The flavor text of a win, by the number of guesses it took minus one.
+    pub win_flavors: Vec<String>,
+    /// The flavor text of a win that took more guesses than there are flavors for.
+    pub win_fallback: String,
+    pub loss: String,
+    pub win_title: String,
+    pub loss_title: String,
+    pub previous_attempts: String,
+    pub untried_letters: String,
+    pub unused_hints: String,
+    pub remaining: String,
+    pub preview: String,
+    pub menu_copy: String,
+    pub menu_copy_board: String,
+    pub menu_quit: String,
+    pub menu_hint: String,
+    pub copied: String,
+    pub share_code: String,
+    pub more_lines: String,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self {
+            about: "wordle-cli (wrdl) is a terminal-based game of Wordle.".to_string(),
+            usage_heading: "USAGE:".to_string(),
+            options_heading: "OPTIONS:".to_string(),
+            controls: "Press *Esc* to quit, *enter* to submit, *tab* for themes, *:* for commands."
+                .to_string(),
+            invalid_guess: "Not a valid five letter word. Try again... ".to_string(),
+            secret_prompt: "Player 1, type a secret word and press *enter*. Player 2, look away!"
+                .to_string(),
+            secret_title: "Secret word".to_string(),
+            did_you_mean: "Not a word — did you mean {word}?".to_string(),
+            suggestion: "Try starting with {word}.".to_string(),
+            theme_changed: "Theme: {theme}".to_string(),
+            gray_hint: "{letter} is not in the word. Hints left: {count}".to_string(),
+            no_hints: "No hints left.".to_string(),
+            unknown_command: "Unknown command: {command}".to_string(),
+            guesses_title: "Guesses {attempts}/{max}".to_string(),
+            alphabet_title: "Alphabets".to_string(),
+            found_letters: "Found: {letters}".to_string(),
+            word_position: "Word {index} of {total}".to_string(),
+            mode_replay: "Replay".to_string(),
+            mode_challenge: "Challenge".to_string(),
+            mode_practice_weak: "Weak letter practice".to_string(),
+            mode_practice: "Practice".to_string(),
+            mode_adversarial: "Adversarial".to_string(),
+            mode_two_player: "Two player game".to_string(),
+            guesses_left: "{count} left".to_string(),
+            too_small: "Terminal too small — please resize".to_string(),
+            win: "{flavor} The word was {word}.".to_string(),
+            win_flavors: [
+                "Genius!",
+                "Magnificent!",
+                "Impressive!",
+                "Splendid!",
+                "Great!",
+                "Phew!",
+            ]
+            .map(String::from)
+            .to_vec(),
+            win_fallback: "Correct!".to_string(),
+            loss: "The correct word was {word}.".to_string(),
+            win_title: "RESULT".to_string(),
+            loss_title: "Result!".to_string(),
+            previous_attempts: "You solved this in {attempts} previously.".to_string(),
+            untried_letters: "Never tried: {letters}".to_string(),
+            unused_hints: "Found but left out of a later guess: {letters}".to_string(),
+            remaining: "{word} → {count} left".to_string(),
+            preview: "{word} would leave about {expected} of {count} possible words, at most \
+                      {most}"
+                .to_string(),
+            menu_copy: "Copy result".to_string(),
+            menu_copy_board: "Copy board".to_string(),
+            menu_quit: "Quit".to_string(),
+            menu_hint: "Use the arrow keys or j/k to move and enter to select".to_string(),
+            copied: "Copied result to clipboard.".to_string(),
+            share_code: "Challenge a friend: wrdl --from-code {code}".to_string(),
+            more_lines: "+{count} more".to_string(),
+        }
+    }
+}
+
+impl Messages {
+    /// Loads the messages for a language code, like `es` or `es_ES.UTF-8`, or from the locale
+    /// file at the given path.
+    pub fn load(lang: &str) -> Result<Self> {
+        let path = Path::new(lang);
+        if path.is_file() {
+            let contents = fs::read(path).map_err(|e| {
+                AppError::io(format!("failed to read locale {}", path.display()), e)
+            })?;
+            return serde_json::from_slice(&contents).map_err(|e| {
+                AppError::InvalidConfig(format!("invalid locale {}: {e}", path.display()))
+            });
+        }
+
+        let code = lang
+            .split(['_', '.', '@', '-'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if matches!(code.as_str(), "en" | "c" | "posix") {
+            return Ok(Self::default());
+        }
+
+        BUNDLED_LOCALES
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, contents)| Ok(serde_json::from_slice(contents)?))
+            .unwrap_or_else(|| {
+                Err(AppError::InvalidArgument(format!(
+                    "unknown language {lang}"
+                )))
+            })
+    }
+
+    /// Loads the messages for the language in the `LANG` environment variable, falling back to
+    /// English when it is unset or unknown.
+    pub fn from_env() -> Self {
+        std::env::var("LANG")
+            .ok()
+            .and_then(|lang| Self::load(&lang).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Replaces each `{name}` placeholder in the message with its value.
+pub fn fill(message: &str, values: &[(&str, &dyn Display)]) -> String {
+    let mut text = message.to_string();
+    for (name, value) in values {
+        text = text.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    text
+}
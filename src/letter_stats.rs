@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use termcolor::{BufferWriter, ColorSpec, WriteColor};
+
+use crate::error::Result;
+use crate::random::Rng;
+use crate::{color, load_file, ui, LetterStatus, Spot, ALPHABETS};
+
+/// How many guesses of a letter it takes for its own rate to count as much as the player's
+/// overall rate, so a letter guessed once isn't judged on that one guess.
+const PRIOR_GUESSES: f64 = 5.0;
+
+/// How often a letter was guessed, and how those guesses turned out.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LetterCount {
+    pub guessed: usize,
+    /// The number of times the letter was in the right position.
+    pub correct: usize,
+    /// The number of times the letter was in the word, but in the wrong position.
+    pub present: usize,
+}
+
+/// How often each letter was guessed across every finished game, keyed by letter.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct LetterStats(BTreeMap<char, LetterCount>);
+
+impl LetterStats {
+    /// Loads the letter stats at the given path.
+    ///
+    /// Returns empty stats if no game has been finished yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        if path.exists() {
+            load_file(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Counts the letters of each guess.
+    pub fn record(&mut self, guesses: &[Vec<Spot>]) {
+        for spot in guesses.iter().flatten() {
+            let count = self.0.entry(spot.letter).or_default();
+            count.guessed += 1;
+            match spot.status {
+                LetterStatus::Correct => count.correct += 1,
+                LetterStatus::Incorrect => count.present += 1,
+                LetterStatus::NotInWord => {},
+            }
+        }
+    }
+
+    /// Returns how much the player struggles with each letter, from 0 for a letter always put
+    /// in the right position to 1 for one never put there, or `None` if no game has been
+    /// finished yet.
+    ///
+    /// Each letter's rate of correct placements is smoothed towards the overall rate by
+    /// [`PRIOR_GUESSES`], and letters never guessed get the overall rate.
+    fn weakness(&self) -> Option<BTreeMap<char, f64>> {
+        let (correct, guessed) = self.0.values().fold((0, 0), |(c, g), count| {
+            (c + count.correct, g + count.guessed)
+        });
+        if guessed == 0 {
+            return None;
+        }
+
+        let overall = correct as f64 / guessed as f64;
+        let weakness = ALPHABETS
+            .iter()
+            .map(|&letter| {
+                let count = self.0.get(&letter).copied().unwrap_or_default();
+                let rate = (count.correct as f64 + PRIOR_GUESSES * overall)
+                    / (count.guessed as f64 + PRIOR_GUESSES);
+                (letter, 1.0 - rate)
+            })
+            .collect();
+        Some(weakness)
+    }
+
+    /// Picks one of the words at random, favouring words with letters the player struggles
+    /// with, and returns its index.
+    ///
+    /// A word's weight is the square of the summed [weakness](Self::weakness) of its distinct
+    /// letters, so words with several weak letters come up much more often than words with one.
+    /// Before any game is finished, or if no letter is weak, every word is as likely.
+    ///
+    /// `words` must not be empty.
+    pub fn pick_weak(&self, words: &[&str], rng: &mut Rng) -> usize {
+        let uniform = |rng: &mut Rng| rng.below(words.len());
+        let Some(weakness) = self.weakness() else {
+            return uniform(rng);
+        };
+
+        let weights: Vec<f64> = words
+            .iter()
+            .map(|word| {
+                let mut letters: Vec<char> = word.chars().map(|c| c.to_ascii_uppercase()).collect();
+                letters.sort_unstable();
+                letters.dedup();
+                let sum: f64 = letters.iter().filter_map(|l| weakness.get(l)).sum();
+                sum * sum
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return uniform(rng);
+        }
+
+        let mut target = rng.unit() * total;
+        for (i, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                return i;
+            }
+            target -= weight;
+        }
+        // rounding can leave a sliver of the total past the last weight
+        words.len() - 1
+    }
+
+    /// Prints how often each letter was guessed and how often it was in the right position,
+    /// laid out like the alphabet panel.
+    pub fn print(&self) -> Result<()> {
+        let bufwtr = BufferWriter::stdout(color::choice());
+        let mut buffer = bufwtr.buffer();
+
+        if self.0.is_empty() {
+            writeln!(&mut buffer, "No games finished yet.")?;
+            bufwtr.print(&buffer)?;
+            return Ok(());
+        }
+
+        for row in ui::alphabet_rows(u16::MAX) {
+            let letters = &ALPHABETS[row];
+            let counts: Vec<_> = letters
+                .iter()
+                .map(|l| self.0.get(l).copied().unwrap_or_default())
+                .collect();
+
+            buffer.set_color(ColorSpec::new().set_bold(true))?;
+            write!(&mut buffer, "{:9}", "")?;
+            for letter in letters {
+                write!(&mut buffer, "{letter:>6}")?;
+            }
+            buffer.reset()?;
+
+            write!(&mut buffer, "\n{:9}", "guessed")?;
+            for count in &counts {
+                write!(&mut buffer, "{:>6}", count.guessed)?;
+            }
+
+            write!(&mut buffer, "\n{:9}", "correct")?;
+            for count in &counts {
+                let percent = (count.correct * 100)
+                    .checked_div(count.guessed)
+                    .map_or("-".to_string(), |p| format!("{p}%"));
+                write!(&mut buffer, "{percent:>6}")?;
+            }
+            writeln!(&mut buffer, "\n")?;
+        }
+        bufwtr.print(&buffer)?;
+
+        Ok(())
+    }
+}
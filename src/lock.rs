@@ -0,0 +1,57 @@
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::error::{AppError, Result};
+
+/// An advisory lock held while the app writes its data, preventing concurrent sessions from
+/// overwriting each other's data.
+///
+/// The operating system releases the lock when the file is closed, so it is also released when
+/// the process is killed without unwinding, like when the terminal is closed mid-game.
+#[derive(Debug)]
+pub struct Lock {
+    // held only to keep the lock until this is dropped
+    _file: File,
+}
+
+impl Lock {
+    /// Acquires the lock on the lock file at the given path, creating the file if needed.
+    ///
+    /// Fails with [`AppError::SessionLocked`] if another process holds the lock.
+    pub fn acquire(path: PathBuf) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                AppError::io(
+                    format!("failed to create data directory at {}", parent.display()),
+                    e,
+                )
+            })?;
+        }
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| {
+                AppError::io(format!("failed to open lock file at {}", path.display()), e)
+            })?;
+        match file.try_lock() {
+            Ok(()) => {},
+            Err(TryLockError::WouldBlock) => return Err(AppError::SessionLocked(path)),
+            Err(TryLockError::Error(e)) => {
+                return Err(AppError::io(
+                    format!("failed to lock {}", path.display()),
+                    e,
+                ))
+            },
+        }
+        // the id only helps a user tell which process holds the lock
+        let _ = file
+            .set_len(0)
+            .and_then(|_| write!(file, "{}", std::process::id()));
+
+        Ok(Self { _file: file })
+    }
+}
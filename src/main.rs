@@ -1,38 +1,121 @@
+mod clock;
+mod color;
 mod error;
+mod events;
+mod history;
+mod i18n;
+mod letter_stats;
+mod lock;
+mod random;
+mod share_code;
+#[cfg(feature = "export-image")]
+mod share_image;
+mod solver;
 mod ui;
 
-use std::collections::HashSet;
-use std::env;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{self, OpenOptions};
-use std::io::Write;
+use std::io::{self, BufRead, Write};
+use std::iter::Peekable;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
+use std::{env, fmt};
 
-use error::{exit, Result};
+use clock::{Clock, SystemClock};
+use error::{exit, AppError, Result};
+use history::History;
+use i18n::Messages;
+use letter_stats::LetterStats;
+use lock::Lock;
+use random::Rng;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use termcolor::{BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
+use share_code::ShareCode;
+use solver::LetterScores;
+use termcolor::{BufferWriter, Color, ColorSpec, WriteColor};
+use ui::{BoardAlignment, Colors, Emojis, GameMode, Puzzle, Settings, Theme};
 
 pub const ALPHABETS: [char; 26] = [
     'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
     'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
 ];
 
+/// The number of games the color legend is shown for before it has to be asked for.
+const LEGEND_GAMES: usize = 3;
+
+/// The number of guesses the player gets when no other budget is configured.
+const DEFAULT_MAX_ATTEMPTS: usize = 6;
+
+/// The name the default word list's pointers are saved under, which a named list can't take.
+const DEFAULT_LIST: &str = "default";
+
 const DEFAULT_WORDS: &[u8] = include_bytes!("../data/words.json");
 const DEFAULT_ALLOWED_GUESSES: &[u8] = include_bytes!("../data/allowed_guesses.json");
 
-const ABOUT: &str = "wordle-cli (wrdl) is a terminal-based game of Wordle.";
-
 const USAGE: &str = "[OPTIONS]";
 
 const OPTIONS: &str = "
+        --a11y                      Label letter statuses with text as well as color
+        --adversarial               Pick the answer as late as possible, to be the hardest to find
     -a, --allowed-guesses [path]    Specify path to allowed guesses file, leave blank to unset
+        --analyze                   Show how many possible answers were left after each guess
+        --assist                    Show hints deduced from previous guesses
+        --attempts <n>              Set the number of guesses allowed in this game
+        --auto-submit               Submit a guess as soon as its last letter is typed
+        --blacklist [path]          Specify path to words never to play as the answer, leave blank to unset
+        --compact-share             Share the result on a single line instead of a grid
+        --debug                     Print where the data and word lists were loaded from
+        --did-you-mean              Suggest the closest word when a guess is not valid
+        --export <path>             Export the data, stats and history to a file
+        --export-csv <path>         Export the history of finished games to a CSV file
+        --export-image <path>       Save the result as a PNG image when the game ends
+        --extra-dict [path]         Specify path to an extra dictionary of guesses, leave blank to unset
+        --from-code <code>          Play the word someone else shared a code for
     -h, --help                      Print help information
+        --import <path>             Replace the data, stats and history with an exported file
+        --info                      Print the data file, word lists and settings in effect
+        --json                      Print the result as json when the game ends
+        --keep-case                 Show the answer with the casing from the word list
+        --lang <code|path>          Show messages in a language, or from a locale file
+        --legend                    Show what the colors mean, toggled with ? during the game
+        --letter-stats              Print how often each letter was guessed and placed correctly
+        --list <name>               Play from a word list named in the data file
+        --merge-guesses <bool>      Whether valid words are accepted as guesses, true by default
+        --newest-first              Play the words from the end of the list to the beginning
+        --no-animation              Don't highlight rejected guesses
+        --no-color                  Don't use colors, also set by the NO_COLOR environment variable
+        --no-keyboard               Hide the alphabet panel during the game
+        --no-mouse                  Let the terminal select text with the mouse during the game
+        --no-repeats                Only play words without repeated letters
+        --no-save                   Play without saving any progress
+        --no-shuffle                Play the words in file order, starting over
+        --notify                    Ring the terminal bell when the game ends
+        --opener [word]             Specify a word to play as the first guess of every game, leave blank to unset
+        --play-from <path>          Play the guesses in a file, one per line, without the interface
+        --practice-weak             Play a random word with letters you often misplace
+        --puzzle <n>                Replay the nth word without moving the next word pointer
+    -q, --quiet                     Don't print informational messages, only results and errors
     -r, --reset                     Set the next word pointer to the beginning
+        --reset-stats               Clear the solve records, letter stats and history, keeping the next word
+        --share-log <path>          Append the shareable result of the game to a file
+        --show-remaining            Show how many guesses are left below the board, colored by how few
+        --shuffle                   Play the words in a random order, starting over
+        --sticky-greens             Show letters already in the right place in the row being typed
+        --strict-guesses            Only accept guesses from the allowed guesses file
+        --suggest-opener            Suggest a strong first guess when the game starts
+        --title                     Show the puzzle number in the window title during the game
+        --two-player                Let one player pick a secret word for another to guess
+        --unlimited                 Keep guessing until the word is found, without counting the game
     -V, --version                   Print version information
     -w, --words [path]              Specify path to allowed words file, leave blank to unset";
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+/// Options for debugging that are only listed by `--help-hidden`.
+const HIDDEN_OPTIONS: &str = "
+        --benchmark                 Run the solver over every word and print how it did
+        --help-hidden               Print help information, including hidden options
+        --show-answer               Print the answer to stderr before the game starts (spoiler)";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Data {
     #[serde(default)]
     index: usize,
@@ -40,6 +123,194 @@ pub struct Data {
     words_path: Option<PathBuf>,
     #[serde(default)]
     allowed_guesses_path: Option<PathBuf>,
+    /// A dictionary consulted for guesses missing from the allowed guesses.
+    #[serde(default)]
+    extra_dict_path: Option<PathBuf>,
+    /// Words that are skipped when they come up as the answer.
+    #[serde(default)]
+    blacklist_path: Option<PathBuf>,
+    #[serde(default)]
+    theme: Theme,
+    #[serde(default)]
+    emojis: Emojis,
+    #[serde(default)]
+    colors: Colors,
+    /// The seed of the order the words are played in, if they are shuffled.
+    #[serde(default)]
+    shuffle_seed: Option<u64>,
+    /// The number of guesses allowed when `--attempts` isn't given.
+    #[serde(default = "default_max_attempts")]
+    max_attempts: usize,
+    /// Whether to always hide the alphabet panel, as if `--no-keyboard` was given.
+    #[serde(default)]
+    hide_keyboard: bool,
+    /// Flavor text shown on a win, by the number of guesses it took.
+    #[serde(default)]
+    win_messages: BTreeMap<usize, String>,
+    /// The number of letters that can be revealed to not be in the word each game.
+    #[serde(default)]
+    gray_hints: usize,
+    /// Where the rows of the board are placed.
+    #[serde(default)]
+    alignment: BoardAlignment,
+    /// The number of spaces between tiles.
+    #[serde(default)]
+    tile_spacing: usize,
+    /// A word played as the first guess of every game.
+    #[serde(default)]
+    opener: Option<String>,
+    /// The number of games played to the end, used to show the color legend to new players.
+    #[serde(default)]
+    games_played: usize,
+    /// Paths to extra word lists, by the name they're selected with using `--list`.
+    #[serde(default)]
+    lists: BTreeMap<String, PathBuf>,
+    /// The index of the next word of each named word list.
+    #[serde(default)]
+    list_indices: BTreeMap<String, usize>,
+    /// The index of the last word played with `--newest-first`, by the name of the word list, or
+    /// `default` for the default list.
+    #[serde(default)]
+    newest_indices: BTreeMap<String, usize>,
+}
+
+impl Default for Data {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            words_path: None,
+            allowed_guesses_path: None,
+            extra_dict_path: None,
+            blacklist_path: None,
+            theme: Theme::default(),
+            emojis: Emojis::default(),
+            colors: Colors::default(),
+            shuffle_seed: None,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            hide_keyboard: false,
+            win_messages: BTreeMap::new(),
+            gray_hints: 0,
+            alignment: BoardAlignment::default(),
+            tile_spacing: 0,
+            opener: None,
+            games_played: 0,
+            lists: BTreeMap::new(),
+            list_indices: BTreeMap::new(),
+            newest_indices: BTreeMap::new(),
+        }
+    }
+}
+
+impl Data {
+    /// Returns the index of the next word of the named word list, or of the default list.
+    fn next_index(&self, list: Option<&str>) -> usize {
+        match list {
+            Some(name) => self.list_indices.get(name).copied().unwrap_or_default(),
+            None => self.index,
+        }
+    }
+
+    /// Moves past the word at the index of the named word list, or of the default list.
+    fn advance(&mut self, list: Option<&str>, index: usize) {
+        match list {
+            Some(name) => {
+                self.list_indices.insert(name.to_string(), index + 1);
+            },
+            None => self.index = index + 1,
+        }
+    }
+
+    /// Returns the index of the next word of the named word list when playing from the end, or
+    /// `None` if the beginning of the list has been reached.
+    fn next_newest_index(&self, list: Option<&str>, len: usize) -> Option<usize> {
+        match self.newest_indices.get(list.unwrap_or(DEFAULT_LIST)) {
+            Some(&last) => last.min(len).checked_sub(1),
+            None => len.checked_sub(1),
+        }
+    }
+
+    /// Moves before the word at the index of the named word list when playing from the end.
+    fn advance_newest(&mut self, list: Option<&str>, index: usize) {
+        self.newest_indices
+            .insert(list.unwrap_or(DEFAULT_LIST).to_string(), index);
+    }
+
+    /// Sets the next word of every word list back to the beginning.
+    fn reset_indices(&mut self) {
+        self.index = 0;
+        self.list_indices.clear();
+        self.newest_indices.clear();
+    }
+}
+
+fn default_max_attempts() -> usize {
+    DEFAULT_MAX_ATTEMPTS
+}
+
+/// The version of the [`Bundle`] format written by this version of the app.
+const BUNDLE_SCHEMA_VERSION: u32 = 1;
+
+/// Everything the app persists, combined into a single file for backups and transfers.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Bundle {
+    schema_version: u32,
+    data: Data,
+    records: HashMap<String, usize>,
+    #[serde(default)]
+    letter_stats: LetterStats,
+    #[serde(default)]
+    history: History,
+}
+
+/// Options that only apply to the game being started.
+#[derive(Clone, Debug, Default)]
+struct Options {
+    #[cfg(feature = "export-image")]
+    export_image: Option<PathBuf>,
+    max_attempts: Option<usize>,
+    /// The 1-based index of a puzzle to replay instead of the next word.
+    puzzle: Option<usize>,
+    benchmark: bool,
+    show_answer: bool,
+    json: bool,
+    debug: bool,
+    /// Whether to print the configuration in effect instead of playing.
+    info: bool,
+    /// A file to append the shareable result of each finished game to.
+    share_log: Option<PathBuf>,
+    /// A code shared by someone else, to play the same word they did.
+    from_code: Option<ShareCode>,
+    /// A file of guesses to play without the interface.
+    play_from: Option<PathBuf>,
+    no_save: bool,
+    suggest_opener: bool,
+    strict_guesses: bool,
+    keep_case: bool,
+    /// The name of the word list to play from, instead of the default one.
+    list: Option<String>,
+    /// Whether to only play words without repeated letters.
+    no_repeats: bool,
+    /// Whether to play the words from the end of the list to the beginning.
+    newest_first: bool,
+    /// Whether to pick a random word weighted towards the player's weak letters.
+    practice_weak: bool,
+    /// The language or locale file given with `--lang`, used instead of `LANG`.
+    lang: Option<String>,
+    settings: Settings,
+}
+
+impl Options {
+    /// Returns the name the next word pointer is kept under, or `None` for the default list.
+    ///
+    /// Playing without repeated letters skips words, so it gets its own pointer for each list.
+    fn progress_key(&self) -> Option<String> {
+        match (&self.list, self.no_repeats) {
+            (Some(name), false) => Some(name.clone()),
+            (Some(name), true) => Some(format!("{name}/no-repeats")),
+            (None, true) => Some(format!("{DEFAULT_LIST}/no-repeats")),
+            (None, false) => None,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Deserialize, Serialize)]
@@ -80,7 +351,8 @@ impl Spot {
     }
 }
 
-#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+/// The status of a guessed letter, ordered from the most to the least known about it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 enum LetterStatus {
     Correct,
     Incorrect,
@@ -88,10 +360,129 @@ enum LetterStatus {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-struct GuessResult {
+pub struct GuessResult {
     word: String,
     guesses: Vec<Vec<Spot>>,
+    max_attempts: usize,
     duration: Duration,
+    /// The number of letters revealed to not be in the word, making this an assisted game.
+    #[serde(default)]
+    hints: usize,
+    /// Whether the game ignored the attempt limit, so it can only end in a win.
+    #[serde(default)]
+    practice: bool,
+    /// Whether the player gave up before using every guess, which counts as a loss.
+    #[serde(default)]
+    gave_up: bool,
+}
+
+impl GuessResult {
+    /// Returns whether the game was played to the end, either won, lost or given up.
+    fn is_finished(&self) -> bool {
+        self.gave_up || self.is_win() || (!self.practice && self.guesses.len() == self.max_attempts)
+    }
+
+    /// Returns the number of guesses allowed, or `None` when there is no limit.
+    fn attempt_limit(&self) -> Option<usize> {
+        (!self.practice).then_some(self.max_attempts)
+    }
+
+    /// Returns whether the last guess was the correct word.
+    fn is_win(&self) -> bool {
+        self.guesses
+            .last()
+            .is_some_and(|g| g.iter().all(|s| matches!(s.status, LetterStatus::Correct)))
+    }
+}
+
+/// The machine-readable summary of a game printed with `--json`.
+#[derive(Debug, Serialize)]
+struct JsonResult<'a> {
+    /// The 1-based number of the puzzle in the word list, or `None` for a two player or
+    /// adversarial game.
+    puzzle: Option<usize>,
+    mode: &'static str,
+    #[serde(flatten)]
+    result: &'a GuessResult,
+    attempts: usize,
+    win: bool,
+    /// Whether the game was played to the end, which a `--play-from` file can stop short of.
+    finished: bool,
+    /// The shareable result, as it would be copied.
+    share: String,
+    /// A code to play the same word with `--from-code`.
+    code: Option<String>,
+}
+
+/// Returns the index of the first word from `start` on that isn't blacklisted.
+///
+/// Returns the length of the list if every remaining word is blacklisted.
+fn skip_blacklisted(words: &[String], start: usize, blacklist: &HashSet<String>) -> usize {
+    (start..words.len())
+        .find(|&i| !blacklist.contains(&words[i].to_ascii_uppercase()))
+        .unwrap_or(words.len().max(start))
+}
+
+/// Returns the index of the first word from `start` back to the beginning that isn't blacklisted.
+fn skip_blacklisted_back(
+    words: &[String],
+    start: usize,
+    blacklist: &HashSet<String>,
+) -> Option<usize> {
+    (0..=start)
+        .rev()
+        .find(|&i| !blacklist.contains(&words[i].to_ascii_uppercase()))
+}
+
+/// Removes repeated words, ignoring case, and words that aren't five letters from the list,
+/// keeping the order of the rest.
+///
+/// Returns the number of duplicates and the number of other words that were removed.
+fn clean_word_list(words: &mut Vec<String>) -> (usize, usize) {
+    let mut seen = HashSet::new();
+    let (mut duplicates, mut invalid) = (0, 0);
+    words.retain(|w| {
+        if w.len() != ui::WORD_LENGTH || !w.chars().all(|c| c.is_ascii_alphabetic()) {
+            invalid += 1;
+            false
+        } else if !seen.insert(w.to_ascii_uppercase()) {
+            duplicates += 1;
+            false
+        } else {
+            true
+        }
+    });
+    (duplicates, invalid)
+}
+
+/// Adds the answers to the allowed guesses, so an answer missing from them can still be won.
+fn merge_guesses(allowed_guesses: &mut HashSet<String>, words: &[String]) {
+    allowed_guesses.extend(words.iter().map(|w| w.to_ascii_uppercase()));
+}
+
+/// Returns the word in uppercase, reusing its allocation.
+fn uppercase(mut word: String) -> String {
+    word.make_ascii_uppercase();
+    word
+}
+
+/// Returns whether no letter appears in the word more than once.
+fn has_distinct_letters(word: &str) -> bool {
+    let mut seen = HashSet::new();
+    word.chars().all(|c| seen.insert(c.to_ascii_uppercase()))
+}
+
+/// Returns the index of the given letter in the English alphabet.
+///
+/// Indexing starts at zero.
+///
+/// Returns [`None`] if the given letter is not present in the English alphabet.
+pub fn letter_to_index(letter: char) -> Option<usize> {
+    if letter.is_alphabetic() {
+        Some((letter.to_ascii_uppercase() as u8 - b'A') as usize)
+    } else {
+        None
+    }
 }
 
 /// Parses json data as a deserializable object.
@@ -104,23 +495,223 @@ fn get_data_path() -> Result<PathBuf> {
     if let Ok(path) = env::var("WORDLE_CLI_DATA") {
         Ok(PathBuf::from(&path))
     } else {
-        dirs_next::data_dir()
-            .map(|d| d.join("wordle-cli/data.json"))
-            .ok_or_else(|| "unable to retrieve home directory path".into())
+        resolve_data_path(
+            dirs_next::data_dir(),
+            dirs_next::home_dir(),
+            env::current_dir().ok(),
+        )
+    }
+}
+
+/// Returns the path of the data file in the first directory that is known.
+///
+/// The platform's data directory is preferred. Minimal systems may not have one, so a
+/// `.wordle-cli` directory in the home directory, and then the current directory, is used
+/// instead.
+fn resolve_data_path(
+    data_dir: Option<PathBuf>,
+    home_dir: Option<PathBuf>,
+    current_dir: Option<PathBuf>,
+) -> Result<PathBuf> {
+    data_dir
+        .map(|d| d.join("wordle-cli/data.json"))
+        .or_else(|| home_dir.map(|d| d.join(".wordle-cli/data.json")))
+        .or_else(|| current_dir.map(|d| d.join(".wordle-cli/data.json")))
+        .ok_or(AppError::DataDirNotFound)
+}
+
+/// Loads a dictionary of extra allowed guesses.
+///
+/// Json files are read as a word list, anything else as one word per line, like the system
+/// `words` file, ignoring anything after a `#`. Only five letter words are kept.
+fn load_dictionary(path: &Path) -> Result<Vec<String>> {
+    let words: Vec<String> = if path
+        .extension()
+        .is_some_and(|e| e == "json" || e == "jsonc")
+    {
+        load_word_list(path)?
+    } else {
+        fs::read_to_string(path)
+            .map_err(|e| AppError::io(format!("failed to read file at {}", path.display()), e))?
+            .lines()
+            .map(|l| l.split('#').next().unwrap_or_default().trim().to_string())
+            .collect()
+    };
+
+    Ok(words
+        .into_iter()
+        .filter(|w| w.len() == 5 && w.chars().all(|c| c.is_ascii_alphabetic()))
+        .collect())
+}
+
+/// Takes the session lock, unless it is already held.
+fn hold_lock(lock: &mut Option<Lock>, data_path: &Path) -> Result<()> {
+    if lock.is_none() {
+        *lock = Some(Lock::acquire(get_lock_path(data_path))?);
+    }
+    Ok(())
+}
+
+/// Returns the path to the lock file held while the app runs, which is kept next to the data
+/// file.
+fn get_lock_path(data_path: &Path) -> PathBuf {
+    data_path.with_file_name("wordle-cli.lock")
+}
+
+/// Returns the path to the solve records file, which is kept next to the data file.
+fn get_records_path(data_path: &Path) -> PathBuf {
+    data_path.with_file_name("records.json")
+}
+
+/// Returns the path to the letter stats file, which is kept next to the data file.
+fn get_letter_stats_path(data_path: &Path) -> PathBuf {
+    data_path.with_file_name("letter_stats.json")
+}
+
+/// Returns the path to the history of finished games, which is kept next to the data file.
+fn get_history_path(data_path: &Path) -> PathBuf {
+    data_path.with_file_name("history.json")
+}
+
+/// Loads the number of guesses each previously solved word took, keyed by word.
+///
+/// Returns an empty map if no word has been solved yet.
+fn load_records(path: &Path) -> Result<HashMap<String, usize>> {
+    if path.exists() {
+        load_file(path)
+    } else {
+        Ok(HashMap::new())
     }
 }
 
 /// Loads file at the given path into a Deserializable object,
 /// returning error if it does not exist.
 fn load_file<P: AsRef<Path>, T: DeserializeOwned>(path: P) -> Result<T> {
-    serde_json::from_str(&fs::read_to_string(path)?).map_err(|e| e.into())
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path)
+        .map_err(|e| AppError::io(format!("failed to read file at {}", path.display()), e))?;
+    serde_json::from_str(&contents).map_err(|e| e.into())
+}
+
+/// Loads the data file, creating it with the defaults if it doesn't exist yet.
+///
+/// The data file is edited by hand, so a file that can't be parsed is reported and left alone
+/// rather than replaced, which would lose the pointer and every setting in it.
+fn load_data(path: &Path) -> Result<Data> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return update_or_create_data(Data::default(), path)
+        },
+        Err(e) => {
+            return Err(AppError::io(
+                format!("failed to read file at {}", path.display()),
+                e,
+            ))
+        },
+    };
+    serde_json::from_str(&contents)
+        .map_err(|e| AppError::InvalidConfig(format!("{}: {e}", path.display())))
+}
+
+/// Loads the word list at the given path.
+///
+/// Files with the `.jsonc` extension may contain `//` and `/* */` comments.
+fn load_word_list<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let contents = fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => AppError::PathNotFound(path.to_path_buf()),
+        _ => AppError::io(format!("failed to read file at {}", path.display()), e),
+    })?;
+    let contents = if path.extension().is_some_and(|e| e == "jsonc") {
+        strip_json_comments(&contents)
+    } else {
+        contents
+    };
+    serde_json::from_str(&contents).map_err(|e| AppError::InvalidWordList(path.to_path_buf(), e))
+}
+
+/// Loads a custom list of answers, removing duplicates and words that aren't five letters.
+///
+/// Returns the words with the number of duplicates and other words removed. A list with no
+/// words left is an error, otherwise it would look like every word had been played.
+fn load_answers(path: &Path) -> Result<(Vec<String>, usize, usize)> {
+    let mut words = load_word_list(path)?;
+    let (duplicates, invalid) = clean_word_list(&mut words);
+    if words.is_empty() {
+        return Err(AppError::EmptyWordList(path.to_path_buf()));
+    }
+    Ok((words, duplicates, invalid))
+}
+
+/// Loads a custom word list, which is an error if it's empty.
+fn load_nonempty_list(path: &Path) -> Result<Vec<String>> {
+    let words: Vec<String> = load_word_list(path)?;
+    if words.is_empty() {
+        return Err(AppError::EmptyWordList(path.to_path_buf()));
+    }
+    Ok(words)
+}
+
+/// Removes `//` and `/* */` comments from json, leaving strings untouched.
+///
+/// Comments are replaced with spaces, so the positions in parsing errors stay useful.
+fn strip_json_comments(json: &str) -> String {
+    let mut stripped = String::with_capacity(json.len());
+    let mut chars = json.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            stripped.push(c);
+            match c {
+                '\\' => stripped.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {},
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                stripped.push(c);
+            },
+            ('/', Some('/')) => {
+                stripped.push(' ');
+                while chars.next_if(|&c| c != '\n').is_some() {
+                    stripped.push(' ');
+                }
+            },
+            ('/', Some('*')) => {
+                chars.next();
+                stripped.push_str("  ");
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    stripped.push(if c == '\n' { c } else { ' ' });
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            },
+            _ => stripped.push(c),
+        }
+    }
+
+    stripped
 }
 
 /// Updates (or creates) the data file at the given path with the provided data.
-fn update_or_create_data<P: AsRef<Path>>(data: Data, path: P) -> Result<Data> {
-    if let Some(parent) = path.as_ref().parent() {
+fn update_or_create_data<P: AsRef<Path>, T: Serialize>(data: T, path: P) -> Result<T> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
         if !parent.exists() {
-            fs::create_dir_all(parent)?;
+            fs::create_dir_all(parent).map_err(|e| {
+                AppError::io(
+                    format!("failed to create data directory at {}", parent.display()),
+                    e,
+                )
+            })?;
         }
     };
 
@@ -128,34 +719,185 @@ fn update_or_create_data<P: AsRef<Path>>(data: Data, path: P) -> Result<Data> {
         .write(true)
         .truncate(true)
         .create(true)
-        .open(path)?;
+        .open(path)
+        .map_err(|e| AppError::io(format!("failed to open data file at {}", path.display()), e))?;
     serde_json::to_writer_pretty(file, &data)?;
 
     Ok(data)
 }
 
-/// Reads the next argument and checks if it's a valid path.
-fn get_and_verify_path(mut args: env::Args) -> Result<Option<PathBuf>> {
-    if let Some(p) = args.next() {
-        let path = PathBuf::from(p);
-        if path.exists() {
-            Ok(Some(path.canonicalize()?))
-        } else {
-            Err("path does not exist".into())
+/// Plays the guesses in the file, one per line, without the interface.
+///
+/// Playing stops once the word is found or the guesses allowed run out, and a guess that isn't
+/// allowed is an error. Blank lines are skipped.
+fn play_from_file(path: &Path, puzzle: &Puzzle, unlimited: bool) -> Result<GuessResult> {
+    let started = SystemClock.now();
+    let contents = fs::read_to_string(path)
+        .map_err(|e| AppError::io(format!("failed to read file at {}", path.display()), e))?;
+
+    let mut result = GuessResult {
+        word: puzzle.word.clone(),
+        guesses: Vec::new(),
+        max_attempts: puzzle.max_attempts,
+        duration: Duration::ZERO,
+        hints: 0,
+        practice: unlimited,
+        gave_up: false,
+    };
+    let guesses = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    for (line, guess) in guesses {
+        if result.is_finished() {
+            break;
+        }
+
+        let guess = guess.trim().to_ascii_uppercase();
+        if !puzzle.allowed_guesses.contains(&guess) {
+            return Err(AppError::InvalidArgument(format!(
+                "{guess} on line {} of {} is not an allowed guess",
+                line + 1,
+                path.display()
+            )));
         }
+        result
+            .guesses
+            .push(solver::get_spots(&guess, &puzzle.word).to_vec());
+    }
+    result.duration = SystemClock
+        .now()
+        .duration_since(started)
+        .unwrap_or_default();
+
+    Ok(result)
+}
+
+/// Appends the text to the file at the path, creating it if it doesn't exist.
+fn append_to_file(path: &Path, text: &str) -> Result<()> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .map_err(|e| AppError::io(format!("failed to append to {}", path.display()), e))
+}
+
+/// Checks that the path is a readable text file and returns its canonical form.
+fn verify_path(path: PathBuf) -> Result<PathBuf> {
+    if !path.exists() {
+        return Err(AppError::PathNotFound(path));
+    }
+    if path.is_dir() {
+        return Err(AppError::NotAFile(path));
+    }
+    // catch files that can't be read as text now, rather than when a game starts
+    match fs::read_to_string(&path) {
+        Ok(_) => Ok(path.canonicalize()?),
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => Err(AppError::NotUtf8(path)),
+        Err(e) => Err(AppError::io(
+            format!("failed to read file at {}", path.display()),
+            e,
+        )),
+    }
+}
+
+/// Reads the next argument, unless it is another option, and checks if it's a valid path.
+fn get_and_verify_path(args: &mut Peekable<env::Args>) -> Result<Option<PathBuf>> {
+    args.next_if(|a| !a.starts_with('-'))
+        .map(|p| verify_path(PathBuf::from(p)))
+        .transpose()
+}
+
+/// Checks if the word can be an opener, returning it in uppercase.
+///
+/// Whether it's an allowed guess is only checked when a game starts, since the lists can change.
+fn verify_opener(word: &str) -> Result<String> {
+    if word.len() == ui::WORD_LENGTH && word.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(word.to_ascii_uppercase())
     } else {
-        Ok(None)
+        Err(AppError::InvalidArgument(format!(
+            "the opener must be a five letter word, got {word}"
+        )))
     }
 }
 
+/// Reads a path from an environment variable, if it is set and not blank, and checks if it's a
+/// valid path.
+fn get_and_verify_env_path(key: &str) -> Result<Option<PathBuf>> {
+    env::var_os(key)
+        .filter(|p| !p.is_empty())
+        .map(|p| verify_path(PathBuf::from(p)))
+        .transpose()
+}
+
+/// Reads the value of the given option from the next argument.
+fn get_value(args: &mut Peekable<env::Args>, option: &str) -> Result<String> {
+    args.next()
+        .ok_or_else(|| AppError::InvalidArgument(format!("{option} requires a value")))
+}
+
+/// Asks the user a yes or no question on the terminal, defaulting to no.
+fn confirm(question: &str) -> Result<bool> {
+    print!("{question} [y/N] ");
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Loads an exported bundle, checking that it can be read by this version of the app.
+fn load_bundle(path: &Path) -> Result<Bundle> {
+    let bundle: Bundle = load_file(path)?;
+    if bundle.schema_version > BUNDLE_SCHEMA_VERSION {
+        return Err(AppError::InvalidConfig(format!(
+            "export schema version {} is newer than the supported version {BUNDLE_SCHEMA_VERSION}",
+            bundle.schema_version
+        )));
+    }
+
+    Ok(bundle)
+}
+
+/// Prints a message to stderr for troubleshooting, if `--debug` was given.
+fn log_debug(enabled: bool, message: fmt::Arguments) -> Result<()> {
+    if !enabled {
+        return Ok(());
+    }
+
+    let bufwtr = BufferWriter::stderr(color::choice());
+    let mut buffer = bufwtr.buffer();
+    buffer.set_color(ColorSpec::new().set_fg(Some(Color::Blue)).set_bold(true))?;
+    write!(&mut buffer, "debug")?;
+    buffer.reset()?;
+    writeln!(&mut buffer, ": {message}")?;
+    bufwtr.print(&buffer)?;
+
+    Ok(())
+}
+
+/// Describes where a word list was loaded from, for debug messages.
+fn describe_source(path: &Option<PathBuf>) -> String {
+    path.as_ref()
+        .map_or("bundled".to_string(), |p| p.display().to_string())
+}
+
 /// Prints the app version.
-fn print_version() {
-    println!("{}", env!("CARGO_PKG_VERSION"));
+fn print_version() -> Result<()> {
+    let bufwtr = BufferWriter::stdout(color::choice());
+    let mut buffer = bufwtr.buffer();
+    writeln!(&mut buffer, "{}", env!("CARGO_PKG_VERSION"))?;
+    bufwtr.print(&buffer)?;
+
+    Ok(())
 }
 
 /// Prints the help text.
-fn print_help() -> Result<()> {
-    let bufwtr = BufferWriter::stdout(ColorChoice::Auto);
+///
+/// Hidden options are only listed when `hidden` is `true`.
+fn print_help(messages: &Messages, hidden: bool) -> Result<()> {
+    let bufwtr = BufferWriter::stdout(color::choice());
     let mut buffer = bufwtr.buffer();
     buffer.set_color(ColorSpec::new().set_fg(Some(Color::Green)).set_bold(true))?;
 
@@ -165,22 +907,43 @@ fn print_help() -> Result<()> {
 
     writeln!(
         &mut buffer,
-        " {}\n{}\n\n{ABOUT}\n",
+        " {}\n{}\n\n{}\n",
         env!("CARGO_PKG_VERSION"),
-        env!("CARGO_PKG_AUTHORS")
+        env!("CARGO_PKG_AUTHORS"),
+        messages.about
     )?;
 
     buffer.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)).set_bold(true))?;
-    writeln!(&mut buffer, "USAGE:")?;
+    writeln!(&mut buffer, "{}", messages.usage_heading)?;
     buffer.reset()?;
 
     writeln!(&mut buffer, "    {bin_name} {USAGE}\n")?;
 
     buffer.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)).set_bold(true))?;
-    write!(&mut buffer, "OPTIONS:")?;
+    write!(&mut buffer, "{}", messages.options_heading)?;
     buffer.reset()?;
 
     writeln!(&mut buffer, "{OPTIONS}")?;
+    if hidden {
+        writeln!(&mut buffer, "{HIDDEN_OPTIONS}")?;
+    }
+
+    bufwtr.print(&buffer)?;
+
+    Ok(())
+}
+
+/// Prints each fact on its own line, with its name highlighted like the headings of the help.
+fn print_info(facts: &[(&str, String)]) -> Result<()> {
+    let bufwtr = BufferWriter::stdout(color::choice());
+    let mut buffer = bufwtr.buffer();
+
+    for (name, value) in facts {
+        buffer.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)).set_bold(true))?;
+        write!(&mut buffer, "{name}:")?;
+        buffer.reset()?;
+        writeln!(&mut buffer, " {value}")?;
+    }
 
     bufwtr.print(&buffer)?;
 
@@ -190,58 +953,1021 @@ fn print_help() -> Result<()> {
 /// Runs the app.
 fn run() -> Result<()> {
     let data_path = get_data_path()?;
-    let mut data =
-        load_file(&data_path).or_else(|_| update_or_create_data(Data::default(), &data_path))?;
+    // taken only before data is written, so reading commands work while a game is running
+    let mut lock = None;
+    let mut data = load_data(&data_path)?;
+    let records_path = get_records_path(&data_path);
+    let letter_stats_path = get_letter_stats_path(&data_path);
+    let history_path = get_history_path(&data_path);
 
-    let mut args = env::args();
-    if let Some(arg) = args.nth(1) {
+    // checked up front so it also quiets the options before it
+    let quiet = env::args().skip(1).any(|a| a == "-q" || a == "--quiet");
+    let mut options = Options::default();
+    let mut configured = false;
+    let mut show_help = false;
+    let mut show_hidden = false;
+    // set by commands that only print something, so the game doesn't start after them
+    let mut printed = false;
+    let mut args = env::args().peekable();
+    args.next();
+    while let Some(arg) = args.next() {
         match arg.as_str() {
-            "-w" | "--words" => data.words_path = get_and_verify_path(args)?,
-            "-a" | "--allowed-guesses" => data.allowed_guesses_path = get_and_verify_path(args)?,
-            "-r" | "--reset" => data.index = 0,
-            "-V" | "--version" => print_version(),
-            "-h" | "--help" => print_help()?,
-            _ => return Err("invalid argument".into()),
+            "-w" | "--words" => data.words_path = get_and_verify_path(&mut args)?,
+            "-a" | "--allowed-guesses" => {
+                data.allowed_guesses_path = get_and_verify_path(&mut args)?
+            },
+            "--extra-dict" => data.extra_dict_path = get_and_verify_path(&mut args)?,
+            "--blacklist" => data.blacklist_path = get_and_verify_path(&mut args)?,
+            "--opener" => {
+                data.opener = args
+                    .next_if(|a| !a.starts_with('-'))
+                    .map(|word| verify_opener(&word))
+                    .transpose()?
+            },
+            "-r" | "--reset" => {
+                let played = data.index;
+                data.reset_indices();
+                let words = if played == 1 { "word" } else { "words" };
+                if !quiet {
+                    println!("reset after {played} {words}, the next word is the first one again");
+                }
+                if data.shuffle_seed.is_some() {
+                    data.shuffle_seed = Some(random::new_seed(&SystemClock));
+                }
+            },
+            "--shuffle" => {
+                data.reset_indices();
+                data.shuffle_seed = Some(random::new_seed(&SystemClock));
+            },
+            "--no-shuffle" => {
+                data.reset_indices();
+                data.shuffle_seed = None;
+            },
+            "--export" => {
+                let path = get_value(&mut args, &arg)?;
+                let bundle = Bundle {
+                    schema_version: BUNDLE_SCHEMA_VERSION,
+                    data: data.clone(),
+                    records: load_records(&records_path)?,
+                    letter_stats: LetterStats::load(&letter_stats_path)?,
+                    history: History::load(&history_path)?,
+                };
+                update_or_create_data(bundle, path)?;
+                printed = true;
+                continue;
+            },
+            "--import" => {
+                hold_lock(&mut lock, &data_path)?;
+                let bundle = load_bundle(Path::new(&get_value(&mut args, &arg)?))?;
+                if confirm("This will overwrite your data, solve records and history. Continue?")? {
+                    data = bundle.data;
+                    update_or_create_data(bundle.records, &records_path)?;
+                    update_or_create_data(bundle.letter_stats, &letter_stats_path)?;
+                    update_or_create_data(bundle.history, &history_path)?;
+                } else if !quiet {
+                    println!("import cancelled");
+                }
+            },
+            "--reset-stats" => {
+                hold_lock(&mut lock, &data_path)?;
+                if confirm(
+                    "This will clear your solve records, letter stats and history. Continue?",
+                )? {
+                    let solved = load_records(&records_path)?.len();
+                    update_or_create_data(HashMap::<String, usize>::new(), &records_path)?;
+                    update_or_create_data(LetterStats::default(), &letter_stats_path)?;
+                    update_or_create_data(History::default(), &history_path)?;
+                    let words = if solved == 1 { "word" } else { "words" };
+                    if !quiet {
+                        println!(
+                            "cleared the records of {solved} solved {words}, the letter stats \
+                             and the history, the next word is unchanged"
+                        );
+                    }
+                } else if !quiet {
+                    println!("reset cancelled");
+                }
+            },
+            "--export-csv" => {
+                let path = PathBuf::from(get_value(&mut args, &arg)?);
+                let history = History::load(&history_path)?;
+                history.export_csv(&path)?;
+                if !quiet {
+                    let games = if history.len() == 1 { "game" } else { "games" };
+                    println!("exported {} {games} to {}", history.len(), path.display());
+                }
+                printed = true;
+                continue;
+            },
+            "-V" | "--version" => {
+                print_version()?;
+                printed = true;
+                continue;
+            },
+            "--letter-stats" => {
+                LetterStats::load(&letter_stats_path)?.print()?;
+                printed = true;
+                continue;
+            },
+            "-h" | "--help" => {
+                show_help = true;
+                continue;
+            },
+            "--help-hidden" => {
+                show_help = true;
+                show_hidden = true;
+                continue;
+            },
+            "--auto-submit" => {
+                options.settings.auto_submit = true;
+                continue;
+            },
+            "-q" | "--quiet" => continue,
+            "--no-mouse" => {
+                options.settings.no_mouse = true;
+                continue;
+            },
+            "--show-remaining" => {
+                options.settings.show_remaining = true;
+                continue;
+            },
+            "--title" => {
+                options.settings.title = true;
+                continue;
+            },
+            "--sticky-greens" => {
+                options.settings.sticky_greens = true;
+                continue;
+            },
+            "--adversarial" => {
+                options.settings.adversarial = true;
+                continue;
+            },
+            "--unlimited" => {
+                options.settings.unlimited = true;
+                continue;
+            },
+            "--notify" => {
+                options.settings.notify = true;
+                continue;
+            },
+            "--compact-share" => {
+                options.settings.compact_share = true;
+                continue;
+            },
+            "--did-you-mean" => {
+                options.settings.did_you_mean = true;
+                continue;
+            },
+            "--legend" => {
+                options.settings.show_legend = true;
+                continue;
+            },
+            "--list" => {
+                options.list = Some(get_value(&mut args, &arg)?);
+                continue;
+            },
+            "--no-animation" => {
+                options.settings.no_animation = true;
+                continue;
+            },
+            "--no-color" => {
+                color::disable();
+                continue;
+            },
+            "--no-repeats" => {
+                options.no_repeats = true;
+                continue;
+            },
+            "--newest-first" => {
+                options.newest_first = true;
+                continue;
+            },
+            "--practice-weak" => {
+                options.practice_weak = true;
+                continue;
+            },
+            "--no-keyboard" => {
+                options.settings.hide_keyboard = true;
+                continue;
+            },
+            "--analyze" => {
+                options.settings.analyze = true;
+                continue;
+            },
+            "--assist" => {
+                options.settings.assist = true;
+                continue;
+            },
+            "--a11y" => {
+                options.settings.a11y = true;
+                continue;
+            },
+            "--keep-case" => {
+                options.keep_case = true;
+                continue;
+            },
+            "--strict-guesses" => {
+                options.strict_guesses = true;
+                continue;
+            },
+            "--merge-guesses" => {
+                let merge = get_value(&mut args, &arg)?;
+                options.strict_guesses = match merge.as_str() {
+                    "true" => false,
+                    "false" => true,
+                    _ => {
+                        return Err(AppError::InvalidArgument(format!(
+                            "{arg} must be true or false, got {merge}"
+                        )))
+                    },
+                };
+                continue;
+            },
+            "--two-player" => {
+                options.settings.two_player = true;
+                continue;
+            },
+            "--suggest-opener" => {
+                options.suggest_opener = true;
+                continue;
+            },
+            "--attempts" => {
+                let attempts = get_value(&mut args, &arg)?;
+                match attempts.parse() {
+                    Ok(n) if n >= 1 => options.max_attempts = Some(n),
+                    _ => {
+                        return Err(AppError::InvalidArgument(format!(
+                            "{arg} must be a positive number, got {attempts}"
+                        )))
+                    },
+                }
+                continue;
+            },
+            "--from-code" => {
+                let code: ShareCode = get_value(&mut args, &arg)?.parse()?;
+                // played like a replay, so it doesn't touch the word pointer or records
+                options.puzzle = Some(code.index + 1);
+                options.no_repeats = code.no_repeats;
+                options.from_code = Some(code);
+                continue;
+            },
+            "--puzzle" => {
+                let puzzle = get_value(&mut args, &arg)?;
+                match puzzle.parse() {
+                    Ok(n) => options.puzzle = Some(n),
+                    Err(_) => {
+                        return Err(AppError::InvalidArgument(format!(
+                            "{arg} must be a number, got {puzzle}"
+                        )))
+                    },
+                }
+                continue;
+            },
+            "--benchmark" => {
+                options.benchmark = true;
+                continue;
+            },
+            "--show-answer" => {
+                options.show_answer = true;
+                continue;
+            },
+            "--no-save" => {
+                options.no_save = true;
+                continue;
+            },
+            "--json" => {
+                options.json = true;
+                continue;
+            },
+            "--debug" => {
+                options.debug = true;
+                continue;
+            },
+            "--info" => {
+                options.info = true;
+                continue;
+            },
+            "--share-log" => {
+                options.share_log = Some(PathBuf::from(get_value(&mut args, &arg)?));
+                continue;
+            },
+            "--play-from" => {
+                options.play_from = Some(PathBuf::from(get_value(&mut args, &arg)?));
+                continue;
+            },
+            "--lang" => {
+                options.lang = Some(get_value(&mut args, &arg)?);
+                continue;
+            },
+            #[cfg(feature = "export-image")]
+            "--export-image" => {
+                options.export_image = Some(PathBuf::from(get_value(&mut args, &arg)?));
+                continue;
+            },
+            _ => return Err(AppError::InvalidArgument(arg)),
         }
-        update_or_create_data(data, data_path)?;
+        configured = true;
+    }
+
+    options.settings.messages = match options.lang {
+        Some(ref lang) => Messages::load(lang)?,
+        None => Messages::from_env(),
+    };
+
+    if show_help {
+        // printed after parsing so `--lang` applies wherever it is given
+        print_help(&options.settings.messages, show_hidden)?;
+    }
+
+    if configured {
+        hold_lock(&mut lock, &data_path)?;
+        data = update_or_create_data(data, &data_path)?;
+        // `--info` goes on to show the settings that were just saved
+        if !options.info {
+            return Ok(());
+        }
+    }
+    if show_help || printed {
         return Ok(());
+    }
+    if !options.info && !options.benchmark {
+        // held until the game's result is saved, and the data is read again so a game that
+        // finished since it was first read isn't undone
+        hold_lock(&mut lock, &data_path)?;
+        data = load_data(&data_path)?;
+    }
+
+    // the environment overrides the saved lists for this run only, without changing the data
+    let words_path = match options.list {
+        // the pointers of a list named like these would be shared with another list's
+        Some(ref name) if name == DEFAULT_LIST || name.contains('/') => {
+            return Err(AppError::InvalidArgument(format!(
+                "the word list name {name} is reserved, rename it in the data file"
+            )))
+        },
+        Some(ref name) => {
+            let path = data.lists.get(name).cloned().ok_or_else(|| {
+                AppError::InvalidArgument(format!("no word list named {name} in the data file"))
+            })?;
+            Some(verify_path(path)?)
+        },
+        None => get_and_verify_env_path("WORDLE_CLI_WORDS")?.or(data.words_path.clone()),
     };
+    let allowed_guesses_path =
+        get_and_verify_env_path("WORDLE_CLI_ALLOWED")?.or(data.allowed_guesses_path.clone());
 
-    let words: Vec<String> = if let Some(ref path) = data.words_path {
-        load_file(path)
-    } else {
-        parse_words_data(DEFAULT_WORDS)
-    }?;
+    log_debug(
+        options.debug,
+        format_args!("data file: {}", data_path.display()),
+    )?;
 
-    let word = words
-        .get(data.index)
-        .ok_or("all available words have been used")?
-        .to_ascii_uppercase();
+    let (mut words, duplicates, invalid) = match words_path {
+        Some(ref path) => load_answers(path)?,
+        None => (parse_words_data(DEFAULT_WORDS)?, 0, 0),
+    };
+    log_debug(
+        options.debug,
+        format_args!("words: {} ({})", describe_source(&words_path), words.len()),
+    )?;
+    if (duplicates > 0 || invalid > 0) && !quiet {
+        let mut removed = Vec::new();
+        if duplicates > 0 {
+            let plural = if duplicates == 1 { "" } else { "s" };
+            removed.push(format!("{duplicates} duplicate{plural}"));
+        }
+        if invalid > 0 {
+            let plural = if invalid == 1 { "" } else { "s" };
+            removed.push(format!("{invalid} word{plural} that aren't five letters"));
+        }
+        eprintln!(
+            "loaded {} words (removed {})",
+            words.len(),
+            removed.join(", ")
+        );
+    }
+    // a code plays the words in the order they were played in by whoever shared it
+    let shuffle_seed = options
+        .from_code
+        .map_or(data.shuffle_seed, |code| code.seed);
+    if let Some(seed) = shuffle_seed {
+        Rng::new(seed).shuffle(&mut words);
+    }
 
-    let mut allowed_guesses: HashSet<String> = if let Some(ref path) = data.allowed_guesses_path {
-        load_file(path)
-    } else {
-        parse_words_data(DEFAULT_ALLOWED_GUESSES)
-    }?;
-    allowed_guesses.extend(words.into_iter());
+    // uppercased as the set is built, so large lists aren't copied again
+    let allowed_list: Vec<String> = match allowed_guesses_path {
+        Some(ref path) => load_nonempty_list(path)?,
+        None => parse_words_data(DEFAULT_ALLOWED_GUESSES)?,
+    };
+    let mut allowed_guesses: HashSet<String> = allowed_list.into_iter().map(uppercase).collect();
+    log_debug(
+        options.debug,
+        format_args!(
+            "allowed guesses: {} ({})",
+            describe_source(&allowed_guesses_path),
+            allowed_guesses.len()
+        ),
+    )?;
+    if let Some(ref path) = data.extra_dict_path {
+        let dictionary = load_dictionary(path)?;
+        log_debug(
+            options.debug,
+            format_args!(
+                "extra dictionary: {} ({})",
+                path.display(),
+                dictionary.len()
+            ),
+        )?;
+        allowed_guesses.extend(dictionary.into_iter().map(uppercase));
+    }
+    if !options.strict_guesses {
+        merge_guesses(&mut allowed_guesses, &words);
+    }
 
-    ui::main(
-        word,
-        allowed_guesses
+    // filtered after the guesses are extended, so the left out words can still be guessed
+    if options.no_repeats {
+        words.retain(|w| has_distinct_letters(w));
+        if words.is_empty() {
+            return Err(AppError::InvalidConfig(
+                "the word list has no words without repeated letters".to_string(),
+            ));
+        }
+    }
+
+    let max_attempts = options.max_attempts.unwrap_or(data.max_attempts).max(1);
+    if options.benchmark {
+        return solver::benchmark(&words, &allowed_guesses, max_attempts);
+    }
+
+    let blacklist: HashSet<String> = match data.blacklist_path {
+        Some(ref path) => load_dictionary(path)?
             .iter()
             .map(|w| w.to_ascii_uppercase())
             .collect(),
-        data.index,
+        None => HashSet::new(),
+    };
+    if words
+        .iter()
+        .all(|w| blacklist.contains(&w.to_ascii_uppercase()))
+    {
+        return Err(AppError::InvalidConfig(
+            "every word in the word list is blacklisted".to_string(),
+        ));
+    }
+
+    let progress_key = options.progress_key();
+    let index = match options.puzzle {
+        Some(n) if (1..=words.len()).contains(&n) => n - 1,
+        Some(n) => {
+            return Err(AppError::InvalidArgument(format!(
+                "puzzle {n} out of range (1..={})",
+                words.len()
+            )))
+        },
+        None if options.practice_weak || options.settings.unlimited => {
+            let playable: Vec<usize> = (0..words.len())
+                .filter(|&i| !blacklist.contains(&words[i].to_ascii_uppercase()))
+                .collect();
+            let mut rng = Rng::new(random::new_seed(&SystemClock));
+            if options.practice_weak {
+                let choices: Vec<&str> = playable.iter().map(|&i| words[i].as_str()).collect();
+                playable[LetterStats::load(&letter_stats_path)?.pick_weak(&choices, &mut rng)]
+            } else {
+                // practice leaves the pointer alone, so the next word would come up every time
+                playable[rng.below(playable.len())]
+            }
+        },
+        // running out of words is reported when the word is looked up below
+        None if options.newest_first => data
+            .next_newest_index(progress_key.as_deref(), words.len())
+            .and_then(|start| skip_blacklisted_back(&words, start, &blacklist))
+            .unwrap_or(words.len()),
+        None => skip_blacklisted(&words, data.next_index(progress_key.as_deref()), &blacklist),
+    };
+    // the answer itself is only shown by `--show-answer`
+    log_debug(
+        options.debug,
+        format_args!("word index: {index} of {} words", words.len()),
     )?;
+    if options.info {
+        let order = match (options.newest_first, data.shuffle_seed) {
+            (true, _) => "newest first",
+            (false, Some(_)) => "shuffled",
+            (false, None) => "in order",
+        };
+        let extra_dict = data
+            .extra_dict_path
+            .as_ref()
+            .map_or("none".to_string(), |p| p.display().to_string());
+        return print_info(&[
+            ("data file", data_path.display().to_string()),
+            ("words", describe_source(&words_path)),
+            ("word count", words.len().to_string()),
+            ("allowed guesses", describe_source(&allowed_guesses_path)),
+            ("extra dictionary", extra_dict),
+            ("allowed guess count", allowed_guesses.len().to_string()),
+            ("next word", format!("{} of {}", index + 1, words.len())),
+            ("order", order.to_string()),
+            ("attempts", max_attempts.to_string()),
+            ("theme", data.theme.name().to_string()),
+        ]);
+    }
+    let two_player = options.settings.two_player;
+    let adversarial = options.settings.adversarial;
+    if two_player && options.play_from.is_some() {
+        return Err(AppError::InvalidArgument(
+            "--play-from can't be used with --two-player".to_string(),
+        ));
+    }
+    if two_player && adversarial {
+        return Err(AppError::InvalidArgument(
+            "--adversarial can't be used with --two-player".to_string(),
+        ));
+    }
+    if adversarial && options.play_from.is_some() {
+        return Err(AppError::InvalidArgument(
+            "--play-from can't be used with --adversarial".to_string(),
+        ));
+    }
+    let (word, display_word) = if two_player || adversarial {
+        // player 1 types the answer once the game starts, and an adversarial game settles on one
+        // as it goes
+        (String::new(), String::new())
+    } else {
+        let original_word = words.get(index).ok_or(AppError::AllWordsUsed)?;
+        let word = original_word.to_ascii_uppercase();
+        let display_word = if options.keep_case {
+            original_word.clone()
+        } else {
+            word.clone()
+        };
+        (word, display_word)
+    };
+    let total = words.len();
+    if options.show_answer && !two_player && !adversarial {
+        // stderr keeps the answer out of the game screen and anything piped from stdout
+        eprintln!("spoiler: the answer is {word}");
+    }
+    // the answer must always be accepted, otherwise the game can't be won
+    if !two_player && !adversarial && !allowed_guesses.contains(&word) {
+        // strict guesses leave out the answers on purpose, so there's nothing to warn about
+        if !options.strict_guesses {
+            eprintln!("warning: the answer is missing from the allowed guesses, adding it");
+        }
+        allowed_guesses.insert(word.clone());
+    }
+
+    if [
+        &data.emojis.correct,
+        &data.emojis.incorrect,
+        &data.emojis.not_in_word,
+    ]
+    .into_iter()
+    .flatten()
+    .any(|e| e.trim().is_empty())
+    {
+        return Err(AppError::InvalidConfig(
+            "custom emojis must not be empty".to_string(),
+        ));
+    }
+
+    let mut settings = options.settings;
+    settings.emojis = data.emojis.clone();
+    settings.colors = data.colors.palette(|name, spec| {
+        eprintln!("warning: invalid {name} color {spec}, using the theme's color");
+    });
+    settings.hide_keyboard |= data.hide_keyboard;
+    settings.win_messages = data.win_messages.clone();
+    // the answer changes as the game goes on, so a hint could turn out wrong
+    settings.gray_hints = if adversarial { 0 } else { data.gray_hints };
+    settings.alignment = data.alignment;
+    settings.tile_spacing = data.tile_spacing;
+    settings.show_legend |= data.games_played < LEGEND_GAMES;
+    settings.no_color = !color::enabled();
+    settings.mode = if two_player {
+        GameMode::TwoPlayer
+    } else if adversarial {
+        GameMode::Adversarial
+    } else if options.from_code.is_some() {
+        // a code also sets the puzzle, so it's checked first
+        GameMode::Challenge
+    } else if options.puzzle.is_some() {
+        GameMode::Replay
+    } else if options.practice_weak {
+        GameMode::PracticeWeak
+    } else if settings.unlimited {
+        GameMode::Practice
+    } else {
+        GameMode::Sequential
+    };
+    let mode = settings.mode;
+    // there's no word to share before player 1 picks it, or before an adversarial game ends
+    let share_code = (!two_player && !adversarial).then(|| {
+        ShareCode {
+            index,
+            seed: shuffle_seed,
+            no_repeats: options.no_repeats,
+        }
+        .to_string()
+    });
+    settings.share_code = share_code.clone();
+    if options.suggest_opener {
+        settings.suggestion = LetterScores::new(&words).best(&allowed_guesses).cloned();
+    }
+    // player 1 hasn't picked the answer yet in a two player game, so there's nothing to score
+    if !two_player {
+        settings.opener = data.opener.clone().filter(|opener| {
+            let allowed = allowed_guesses.contains(opener);
+            if !allowed {
+                eprintln!("warning: the opener {opener} is not an allowed guess, skipping it");
+            }
+            allowed
+        });
+    }
+    let mut records = load_records(&records_path)?;
+
+    let candidates = if adversarial {
+        // the answer can be any word that could come up, and has to be a guess that can win
+        let candidates: Vec<_> = words
+            .iter()
+            .map(|w| w.to_ascii_uppercase())
+            .filter(|w| !blacklist.contains(w))
+            .collect();
+        allowed_guesses.extend(candidates.iter().cloned());
+        candidates
+    } else if settings.analyze || settings.assist {
+        words.iter().map(|w| w.to_ascii_uppercase()).collect()
+    } else {
+        Vec::new()
+    };
+    let puzzle = Puzzle {
+        previous_attempts: records.get(&word).copied().filter(|_| !adversarial),
+        candidates,
+        display_word,
+        word,
+        allowed_guesses,
+        index,
+        // newest first plays the list from the end, so the index counts down
+        played: if options.newest_first {
+            total.saturating_sub(index + 1)
+        } else {
+            index
+        },
+        total,
+        max_attempts,
+    };
+    let compact_share = settings.compact_share;
+    let result = match options.play_from {
+        Some(ref path) => {
+            let result = play_from_file(path, &puzzle, settings.unlimited)?;
+            // the json result replaces the board, so stdout stays machine readable
+            if !options.json {
+                print!("{}", ui::board_text(mode, index, &result));
+                match (result.is_win(), result.is_finished()) {
+                    (true, _) => println!("\nsolved in {}", result.guesses.len()),
+                    (false, true) => println!("\nnot solved, the word was {}", puzzle.display_word),
+                    (false, false) => println!("\nout of guesses to play"),
+                }
+            }
+            result
+        },
+        None => ui::main(puzzle, settings, &mut data.theme, &SystemClock)?,
+    };
+
+    if result.is_finished() {
+        #[cfg(feature = "export-image")]
+        if let Some(path) = options.export_image {
+            let header = ui::result_header(mode, index, &result);
+            share_image::export(&result, &header, data.theme, path)?;
+        }
+
+        if let Some(ref path) = options.share_log {
+            let text = ui::share_text(
+                mode,
+                index,
+                &result,
+                data.theme,
+                &data.emojis,
+                compact_share,
+            );
+            let date = clock::format_date(SystemClock.now());
+            // the game is already over, so a failed write shouldn't lose the rest of it
+            if let Err(e) = append_to_file(path, &format!("{date}\n{text}\n")) {
+                eprintln!("warning: {e}");
+            }
+        }
+    }
+
+    // a file of guesses can run out before the game ends, which still moves the word pointer
+    if options.json && (result.is_finished() || options.play_from.is_some()) {
+        let json = JsonResult {
+            puzzle: mode.puzzle_number(index),
+            mode: match mode {
+                GameMode::Adversarial | GameMode::TwoPlayer => mode.name(),
+                _ if result.practice => "practice",
+                _ => mode.name(),
+            },
+            result: &result,
+            attempts: result.guesses.len(),
+            win: result.is_win(),
+            finished: result.is_finished(),
+            share: ui::share_text(
+                mode,
+                index,
+                &result,
+                data.theme,
+                &data.emojis,
+                compact_share,
+            ),
+            code: share_code,
+        };
+        println!("{}", serde_json::to_string(&json)?);
+    }
+
+    if options.no_save {
+        // stderr keeps the notice out of any json printed to stdout
+        if !quiet {
+            eprintln!("(dry run — progress not saved)");
+        }
+        return Ok(());
+    }
+
+    if result.practice {
+        // practice games don't count towards the solve records or letter stats
+        update_or_create_data(data, data_path)?;
+        return Ok(());
+    }
+
+    if options.puzzle.is_some() || two_player || adversarial {
+        // replays, two player and adversarial games keep the theme but leave the word pointer,
+        // solve records, letter stats and history alone
+        update_or_create_data(data, data_path)?;
+        return Ok(());
+    }
 
-    data.index += 1;
+    if result.is_finished() {
+        data.games_played += 1;
+        let mut letter_stats = LetterStats::load(&letter_stats_path)?;
+        letter_stats.record(&result.guesses);
+        update_or_create_data(letter_stats, &letter_stats_path)?;
+
+        let mut history = History::load(&history_path)?;
+        history.record(history::Game {
+            date: clock::format_date(SystemClock.now()),
+            puzzle: index + 1,
+            word: result.word.clone(),
+            attempts: result.guesses.len(),
+            win: result.is_win(),
+            duration: result.duration,
+        });
+        update_or_create_data(history, &history_path)?;
+    }
+
+    if options.practice_weak {
+        // weak letter practice counts towards the letter stats it's picked from, but the word
+        // didn't come from the pointer
+        update_or_create_data(data, data_path)?;
+        return Ok(());
+    }
+
+    if result.is_win() {
+        records.insert(result.word.clone(), result.guesses.len());
+        update_or_create_data(records, records_path)?;
+    }
+
+    if options.newest_first {
+        data.advance_newest(progress_key.as_deref(), index);
+    } else {
+        data.advance(progress_key.as_deref(), index);
+    }
     update_or_create_data(data, data_path)?;
 
     Ok(())
 }
 
 fn main() {
-    if let Err(e) = run() {
-        exit(e, 1);
+    match run() {
+        // the reader went away, like `head` closing the pipe, so there's no one to tell
+        Err(e) if e.is_broken_pipe() => {},
+        Err(e) => exit(e),
+        Ok(()) => {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a path in a directory of its own under the system's temporary directory, so tests
+    /// running at the same time don't share files.
+    fn temp_path(test: &str, name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("wordle-cli-{}-{test}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    #[test]
+    fn data_path_prefers_the_data_dir_then_home_then_current_dir() {
+        let data = Some(PathBuf::from("/data"));
+        let home = Some(PathBuf::from("/home/alice"));
+        let current = Some(PathBuf::from("/work"));
+        assert_eq!(
+            resolve_data_path(data, home.clone(), current.clone()).unwrap(),
+            Path::new("/data/wordle-cli/data.json")
+        );
+        assert_eq!(
+            resolve_data_path(None, home, current.clone()).unwrap(),
+            Path::new("/home/alice/.wordle-cli/data.json")
+        );
+        assert_eq!(
+            resolve_data_path(None, None, current).unwrap(),
+            Path::new("/work/.wordle-cli/data.json")
+        );
+        assert!(matches!(
+            resolve_data_path(None, None, None),
+            Err(AppError::DataDirNotFound)
+        ));
+    }
+
+    #[test]
+    fn data_path_comes_from_the_environment_first() {
+        let path = "/somewhere/else/data.json";
+        env::set_var("WORDLE_CLI_DATA", path);
+        let resolved = get_data_path();
+        env::remove_var("WORDLE_CLI_DATA");
+        assert_eq!(resolved.unwrap(), Path::new(path));
+    }
+
+    #[test]
+    fn exported_bundle_imports_unchanged() {
+        let mut letter_stats = LetterStats::default();
+        letter_stats.record(&[solver::get_spots("CRANE", "SLATE").to_vec()]);
+        let mut history = History::default();
+        history.record(history::Game {
+            date: "2026-10-16".to_string(),
+            puzzle: 43,
+            word: "SLATE".to_string(),
+            attempts: 2,
+            win: true,
+            duration: Duration::from_secs(75),
+        });
+        let bundle = Bundle {
+            schema_version: BUNDLE_SCHEMA_VERSION,
+            data: Data {
+                index: 42,
+                theme: Theme::HighContrast,
+                ..Data::default()
+            },
+            records: HashMap::from([("SLATE".to_string(), 2)]),
+            letter_stats,
+            history,
+        };
+
+        let path = temp_path("bundle", "export.json");
+        update_or_create_data(bundle.clone(), &path).unwrap();
+        let imported = load_bundle(&path).unwrap();
+        assert_eq!(
+            serde_json::to_value(imported).unwrap(),
+            serde_json::to_value(bundle).unwrap()
+        );
+    }
+
+    #[test]
+    fn bundle_from_a_newer_version_is_refused() {
+        let bundle = Bundle {
+            schema_version: BUNDLE_SCHEMA_VERSION + 1,
+            data: Data::default(),
+            records: HashMap::new(),
+            letter_stats: LetterStats::default(),
+            history: History::default(),
+        };
+        let path = temp_path("newer-bundle", "export.json");
+        update_or_create_data(bundle, &path).unwrap();
+        assert!(matches!(
+            load_bundle(&path),
+            Err(AppError::InvalidConfig(message)) if message.contains("newer")
+        ));
+    }
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn selection_skips_blacklisted_words() {
+        let list = words(&["crane", "slate", "ensue", "pious"]);
+        let blacklist = HashSet::from(["SLATE".to_string(), "ENSUE".to_string()]);
+        assert_eq!(skip_blacklisted(&list, 0, &blacklist), 0);
+        assert_eq!(skip_blacklisted(&list, 1, &blacklist), 3);
+        assert_eq!(skip_blacklisted_back(&list, 2, &blacklist), Some(0));
+    }
+
+    #[test]
+    fn selection_past_the_last_allowed_word_runs_out() {
+        let list = words(&["crane", "slate"]);
+        let blacklist = HashSet::from(["SLATE".to_string(), "CRANE".to_string()]);
+        assert_eq!(skip_blacklisted(&list, 1, &blacklist), 2);
+        assert_eq!(skip_blacklisted(&list, 5, &blacklist), 5);
+        assert_eq!(skip_blacklisted_back(&list, 1, &blacklist), None);
+    }
+
+    #[test]
+    fn cleaning_removes_duplicates_and_words_of_the_wrong_length() {
+        let mut list = words(&[
+            "crane", "Slate", "CRANE", "toolong", "slate", "four", "pi0us", "ensue",
+        ]);
+        assert_eq!(clean_word_list(&mut list), (2, 3));
+        assert_eq!(list, ["crane", "Slate", "ensue"]);
+    }
+
+    #[test]
+    fn merging_makes_every_answer_a_guess() {
+        let mut allowed_guesses = HashSet::from(["CRANE".to_string()]);
+        merge_guesses(&mut allowed_guesses, &words(&["crane", "zorro"]));
+        assert_eq!(
+            allowed_guesses,
+            HashSet::from(["CRANE".to_string(), "ZORRO".to_string()])
+        );
+    }
+
+    #[test]
+    fn empty_word_lists_are_refused() {
+        let path = temp_path("empty-lists", "words.json");
+        fs::write(&path, "[]").unwrap();
+        assert!(matches!(load_answers(&path), Err(AppError::EmptyWordList(p)) if p == path));
+        assert!(matches!(load_nonempty_list(&path), Err(AppError::EmptyWordList(p)) if p == path));
+
+        // nothing is left once the words that aren't five letters are removed
+        fs::write(&path, r#"["toolong", "four"]"#).unwrap();
+        assert!(matches!(
+            load_answers(&path),
+            Err(AppError::EmptyWordList(_))
+        ));
+        assert_eq!(load_nonempty_list(&path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn word_file_paths_must_be_readable_text_files() {
+        let path = temp_path("verify-path", "words.json");
+        let dir = path.parent().unwrap().to_path_buf();
+        assert!(matches!(verify_path(dir.clone()), Err(AppError::NotAFile(p)) if p == dir));
+        assert_eq!(
+            AppError::NotAFile(dir.clone()).to_string(),
+            format!("path is a directory, expected a file: {}", dir.display())
+        );
+
+        fs::write(&path, b"[\"caf\xe9s\"]").unwrap();
+        assert!(matches!(verify_path(path.clone()), Err(AppError::NotUtf8(p)) if p == path));
+
+        let missing = dir.join("missing.json");
+        assert!(matches!(
+            verify_path(missing),
+            Err(AppError::PathNotFound(_))
+        ));
+
+        fs::write(&path, "[\"crane\"]").unwrap();
+        assert_eq!(
+            verify_path(path.clone()).unwrap(),
+            path.canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn strips_line_and_block_comments() {
+        let json = "[\"crane\", // a comment\n/* a\nblock */ \"slate\"]";
+        let stripped = strip_json_comments(json);
+        assert_eq!(stripped.len(), json.len());
+        assert_eq!(stripped.lines().count(), json.lines().count());
+        let words: Vec<String> = serde_json::from_str(&stripped).unwrap();
+        assert_eq!(words, ["crane", "slate"]);
+    }
+
+    #[test]
+    fn leaves_comment_markers_in_strings_alone() {
+        let json = r#"["http://x", "/* not a comment */", "a \"// quoted\" b"] // end"#;
+        let words: Vec<String> = serde_json::from_str(&strip_json_comments(json)).unwrap();
+        assert_eq!(words, [
+            "http://x",
+            "/* not a comment */",
+            "a \"// quoted\" b"
+        ]);
+    }
+
+    #[test]
+    fn unterminated_block_comment_runs_to_the_end() {
+        let stripped = strip_json_comments("[\"crane\"] /* never closed\n\"slate\"");
+        assert_eq!(stripped.trim_end(), "[\"crane\"]");
+    }
+
+    #[test]
+    fn text_dictionary_skips_hash_comments() {
+        let path = temp_path("hash-comments", "words.txt");
+        fs::write(
+            &path,
+            "# a list\ncrane # the opener\n\n  slate\nshorter\n#pious\n",
+        )
+        .unwrap();
+        assert_eq!(load_dictionary(&path).unwrap(), ["crane", "slate"]);
     }
 }
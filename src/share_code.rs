@@ -0,0 +1,126 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::AppError;
+
+/// The alphabet of Crockford's base32, which leaves out letters that are easily confused.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Set in the first byte when the words were shuffled, in which case the seed follows the index.
+const SHUFFLED: u8 = 1;
+/// Set in the first byte when only words without repeated letters were played.
+const NO_REPEATS: u8 = 2;
+
+/// A code that lets someone else play the same word, as long as they use the same word list.
+///
+/// The code is the index of the word and how the list was ordered, written in base32 with a
+/// checksum so mistyped codes are caught.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ShareCode {
+    /// The index of the word in the ordered word list.
+    pub index: usize,
+    /// The seed the words were shuffled with, if they were.
+    pub seed: Option<u64>,
+    /// Whether words with repeated letters were left out of the list.
+    pub no_repeats: bool,
+}
+
+impl fmt::Display for ShareCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut flags = 0;
+        if self.seed.is_some() {
+            flags |= SHUFFLED;
+        }
+        if self.no_repeats {
+            flags |= NO_REPEATS;
+        }
+
+        let mut bytes = vec![flags];
+        bytes.extend_from_slice(&(self.index as u32).to_be_bytes());
+        if let Some(seed) = self.seed {
+            bytes.extend_from_slice(&seed.to_be_bytes());
+        }
+        bytes.push(checksum(&bytes));
+
+        write!(f, "{}", encode(&bytes))
+    }
+}
+
+impl FromStr for ShareCode {
+    type Err = AppError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            |reason: &str| AppError::InvalidArgument(format!("invalid code {code}: {reason}"));
+
+        let bytes = decode(code).ok_or_else(|| invalid("it has a character that isn't allowed"))?;
+        let (checked, sum) = match bytes.split_last() {
+            Some((&sum, checked)) if !checked.is_empty() => (checked, sum),
+            _ => return Err(invalid("it is too short")),
+        };
+        if checksum(checked) != sum {
+            return Err(invalid("it may have been mistyped"));
+        }
+
+        let flags = checked[0];
+        let expected_len = if flags & SHUFFLED != 0 { 13 } else { 5 };
+        if flags & !(SHUFFLED | NO_REPEATS) != 0 || checked.len() != expected_len {
+            return Err(invalid("it isn't a wordle-cli code"));
+        }
+
+        let index = u32::from_be_bytes(checked[1..5].try_into().unwrap_or_default()) as usize;
+        let seed = (flags & SHUFFLED != 0)
+            .then(|| u64::from_be_bytes(checked[5..13].try_into().unwrap_or_default()));
+
+        Ok(Self {
+            index,
+            seed,
+            no_repeats: flags & NO_REPEATS != 0,
+        })
+    }
+}
+
+/// Returns a byte that changes when any byte, or the order of the bytes, changes.
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0u8, |sum, &b| sum.rotate_left(3).wrapping_add(b))
+}
+
+/// Writes the bytes in base32, five bits to a character.
+fn encode(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | u16::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            text.push(ALPHABET[usize::from((buffer >> bits) & 31)] as char);
+        }
+    }
+    if bits > 0 {
+        text.push(ALPHABET[usize::from((buffer << (5 - bits)) & 31)] as char);
+    }
+    text
+}
+
+/// Reads base32 written by [`encode`], ignoring case and dashes, or returns `None` if there's a
+/// character outside the alphabet.
+fn decode(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for c in text.chars().filter(|&c| c != '-') {
+        let c = u8::try_from(c.to_ascii_uppercase()).ok()?;
+        let value = ALPHABET.iter().position(|&a| a == c)? as u16;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
@@ -0,0 +1,188 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::error::{AppError, Result};
+use crate::ui::Theme;
+use crate::{GuessResult, LetterStatus};
+
+const BACKGROUND: [u8; 3] = [18, 18, 19];
+const FOREGROUND: [u8; 3] = [248, 248, 248];
+
+const MARGIN: usize = 24;
+const TILE: usize = 48;
+const GAP: usize = 6;
+const FONT_SCALE: usize = 4;
+
+/// Width and height of a glyph in [`glyph`], in font pixels.
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+
+/// An RGB image that can be drawn on and encoded as a PNG.
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for _ in 0..width * height {
+            pixels.extend_from_slice(&BACKGROUND);
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: [u8; 3]) {
+        for row in y..(y + height).min(self.height) {
+            for col in x..(x + width).min(self.width) {
+                let offset = (row * self.width + col) * 3;
+                self.pixels[offset..offset + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// Draws the text with its top left corner at the given position.
+    ///
+    /// Characters without a glyph are drawn as blank space.
+    fn draw_text(&mut self, x: usize, y: usize, text: &str) {
+        for (i, c) in text.chars().enumerate() {
+            let left = x + i * (GLYPH_WIDTH + 1) * FONT_SCALE;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                        self.fill_rect(
+                            left + col * FONT_SCALE,
+                            y + row * FONT_SCALE,
+                            FONT_SCALE,
+                            FONT_SCALE,
+                            FOREGROUND,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the rows of a 5x7 bitmap glyph for the characters used in the result header.
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+    match c.to_ascii_uppercase() {
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        '/' => [0x01, 0x01, 0x02, 0x04, 0x08, 0x10, 0x10],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        '∞' => [0x00, 0x00, 0x0A, 0x15, 0x0A, 0x00, 0x00],
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'D' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        _ => [0; GLYPH_HEIGHT],
+    }
+}
+
+/// Returns the RGB color of a tile with the given status.
+fn rgb_from_status(status: LetterStatus, theme: Theme) -> [u8; 3] {
+    match (theme, status) {
+        (Theme::Normal, LetterStatus::Correct) => [83, 141, 78],
+        (Theme::Normal, LetterStatus::Incorrect) => [181, 159, 59],
+        (Theme::HighContrast, LetterStatus::Correct) => [245, 121, 58],
+        (Theme::HighContrast, LetterStatus::Incorrect) => [133, 192, 249],
+        (Theme::Monochrome, LetterStatus::Correct) => [248, 248, 248],
+        (Theme::Monochrome, LetterStatus::Incorrect) => [160, 160, 160],
+        (_, LetterStatus::NotInWord) => [58, 58, 60],
+    }
+}
+
+/// Renders the result header and status grid of a game as a PNG at the given path.
+pub fn export<P: AsRef<Path>>(
+    result: &GuessResult,
+    header: &str,
+    theme: Theme,
+    path: P,
+) -> Result<()> {
+    let path = path.as_ref();
+    let columns = result
+        .guesses
+        .iter()
+        .map(Vec::len)
+        .max()
+        .unwrap_or_default();
+
+    let header_width = header.chars().count() * (GLYPH_WIDTH + 1) * FONT_SCALE;
+    let header_height = GLYPH_HEIGHT * FONT_SCALE;
+    let grid_width = (columns * (TILE + GAP)).saturating_sub(GAP);
+    let grid_height = (result.guesses.len() * (TILE + GAP)).saturating_sub(GAP);
+
+    let width = header_width.max(grid_width) + 2 * MARGIN;
+    let height = header_height + grid_height + 3 * MARGIN;
+    let mut canvas = Canvas::new(width, height);
+
+    canvas.draw_text((width - header_width) / 2, MARGIN, header);
+
+    let grid_left = (width - grid_width) / 2;
+    let grid_top = header_height + 2 * MARGIN;
+    for (row, guess) in result.guesses.iter().enumerate() {
+        for (col, spot) in guess.iter().enumerate() {
+            canvas.fill_rect(
+                grid_left + col * (TILE + GAP),
+                grid_top + row * (TILE + GAP),
+                TILE,
+                TILE,
+                rgb_from_status(spot.status, theme),
+            );
+        }
+    }
+
+    let context = || format!("failed to write image to {}", path.display());
+    let file = File::create(path).map_err(|e| AppError::io(context(), e))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&canvas.pixels))
+        .map_err(|e| AppError::io(context(), e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_header_character_has_a_glyph() {
+        let headers = [
+            "Wordle 1234567890 X/6",
+            "Wordle (two player) 3/6",
+            "Wordle (adversarial) 5/∞",
+        ];
+        for c in headers.concat().chars().filter(|&c| c != ' ') {
+            assert_ne!(glyph(c), [0; GLYPH_HEIGHT], "no glyph for {c:?}");
+        }
+    }
+}
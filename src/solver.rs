@@ -0,0 +1,295 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+use crate::error::Result;
+use crate::{letter_to_index, LetterStatus, Spot};
+
+/// Returns the status of each letter of the input when guessing the given word.
+///
+/// Like the real game, a letter guessed more times than the word has it is only marked as many
+/// times as the word has it, with letters in the right position marked first and the rest from
+/// left to right.
+pub fn get_spots(input: &str, word: &str) -> [Spot; 5] {
+    let mut spots = [Spot::default(); 5];
+    // the letters of the word that aren't guessed in their position, left for misplaced letters
+    let mut unmatched = [0usize; 26];
+
+    let mut word = word.chars();
+    for (index, letter) in input.chars().enumerate() {
+        let expected = word.next();
+        if Some(letter) == expected {
+            spots[index] = Spot::correct(letter);
+        } else {
+            spots[index] = Spot::not_in_word(letter);
+            if let Some(count) = expected
+                .and_then(letter_to_index)
+                .and_then(|i| unmatched.get_mut(i))
+            {
+                *count += 1;
+            }
+        }
+    }
+
+    for spot in &mut spots {
+        if spot.status != LetterStatus::NotInWord {
+            continue;
+        }
+        let count = letter_to_index(spot.letter).and_then(|i| unmatched.get_mut(i));
+        if let Some(count) = count.filter(|c| **c > 0) {
+            *count -= 1;
+            *spot = Spot::incorrect(spot.letter);
+        }
+    }
+
+    spots
+}
+
+/// Returns whether the candidate could be the answer, given the statuses a guess received.
+pub fn is_consistent(candidate: &str, guess: &[Spot]) -> bool {
+    let input: String = guess.iter().map(|s| s.letter).collect();
+    get_spots(&input, candidate)
+        .iter()
+        .zip(guess)
+        .all(|(a, b)| a.status == b.status)
+}
+
+/// Returns the candidates that would give the guess the statuses shared by the most candidates.
+///
+/// This is the feedback that rules out the fewest possible answers. Ties go to the feedback with
+/// the fewest correct and present letters, so a guess is only marked as the answer when it's
+/// the last candidate left.
+pub fn largest_partition(guess: &str, candidates: &[String]) -> Vec<String> {
+    partitions(guess, candidates)
+        .into_iter()
+        .max_by_key(|(statuses, words)| {
+            let found = statuses
+                .iter()
+                .filter(|&&s| s != LetterStatus::NotInWord)
+                .count();
+            (words.len(), Reverse(found), Reverse(words[0]))
+        })
+        .map(|(_, words)| words.into_iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Returns the number of candidates expected to be left after the guess, and the most that
+/// could be left, if every candidate is equally likely to be the answer.
+///
+/// This only looks at the candidates, not the answer, so it gives nothing away.
+pub fn remaining_after(guess: &str, candidates: &[String]) -> (f64, usize) {
+    let sizes: Vec<usize> = partitions(guess, candidates)
+        .values()
+        .map(Vec::len)
+        .collect();
+    let expected = sizes.iter().map(|&n| n * n).sum::<usize>() as f64 / candidates.len() as f64;
+    (expected, sizes.into_iter().max().unwrap_or_default())
+}
+
+/// Groups the candidates by the statuses the guess would get if each was the answer.
+fn partitions<'a>(
+    guess: &str,
+    candidates: &'a [String],
+) -> HashMap<Vec<LetterStatus>, Vec<&'a String>> {
+    let mut partitions: HashMap<_, Vec<_>> = HashMap::new();
+    for candidate in candidates {
+        let statuses = get_spots(guess, candidate).map(|s| s.status).to_vec();
+        partitions.entry(statuses).or_default().push(candidate);
+    }
+    partitions
+}
+
+/// Scores words by how common their distinct letters are in a word list.
+pub struct LetterScores([usize; 26]);
+
+impl LetterScores {
+    /// Counts the number of words each letter appears in.
+    pub fn new<'a>(words: impl IntoIterator<Item = &'a String>) -> Self {
+        let mut frequencies = [0; 26];
+        for word in words {
+            let mut seen = [false; 26];
+            for index in word.chars().filter_map(letter_to_index) {
+                if !seen[index] {
+                    seen[index] = true;
+                    frequencies[index] += 1;
+                }
+            }
+        }
+
+        Self(frequencies)
+    }
+
+    /// Returns the sum of the frequencies of the word's distinct letters.
+    pub fn score(&self, word: &str) -> usize {
+        let mut seen = [false; 26];
+        word.chars()
+            .filter_map(letter_to_index)
+            .filter(|&i| !std::mem::replace(&mut seen[i], true))
+            .map(|i| self.0[i])
+            .sum()
+    }
+
+    /// Returns the five letter word with the highest score.
+    ///
+    /// Ties are broken alphabetically so the result is stable across runs.
+    pub fn best<'a>(&self, words: impl IntoIterator<Item = &'a String>) -> Option<&'a String> {
+        words
+            .into_iter()
+            .filter(|w| w.len() == 5)
+            .max_by_key(|w| (self.score(w), Reverse(w.as_str())))
+    }
+}
+
+/// Plays the game for the given answer, returning the guesses made.
+///
+/// Every guess after the opener is the best scoring word that is still consistent with the
+/// statuses received so far, with letters scored against those remaining words.
+pub fn solve(answer: &str, words: &[String], opener: &str, max_attempts: usize) -> Vec<String> {
+    let mut candidates: Vec<&String> = words.iter().collect();
+    let mut guesses = vec![opener.to_string()];
+
+    while guesses.len() < max_attempts && guesses.last().map(String::as_str) != Some(answer) {
+        let spots = get_spots(guesses.last().unwrap(), answer);
+        candidates.retain(|c| is_consistent(c, &spots));
+
+        match LetterScores::new(candidates.iter().copied()).best(candidates.iter().copied()) {
+            Some(guess) => guesses.push(guess.clone()),
+            None => break,
+        }
+    }
+
+    guesses
+}
+
+/// Runs the solver against every word and prints a summary of how it did.
+pub fn benchmark(
+    words: &[String],
+    allowed_guesses: &HashSet<String>,
+    max_attempts: usize,
+) -> Result<()> {
+    let words: Vec<String> = words
+        .iter()
+        .filter(|w| w.len() == 5)
+        .map(|w| w.to_ascii_uppercase())
+        .collect();
+    let allowed_guesses: Vec<String> = allowed_guesses
+        .iter()
+        .map(|w| w.to_ascii_uppercase())
+        .collect();
+    let opener = LetterScores::new(&words)
+        .best(allowed_guesses.iter().chain(&words))
+        .cloned()
+        .unwrap_or_default();
+
+    let mut results = Vec::with_capacity(words.len());
+    for (i, word) in words.iter().enumerate() {
+        if i % 50 == 0 {
+            eprint!("\rsolving {}/{}", i + 1, words.len());
+            io::stderr().flush()?;
+        }
+        let guesses = solve(word, &words, &opener, max_attempts);
+        let solved = guesses.last() == Some(word);
+        results.push((word, guesses.len(), solved));
+    }
+    eprintln!("\rsolved {0}/{0}     ", words.len());
+
+    let wins: Vec<_> = results.iter().filter(|r| r.2).collect();
+    let win_rate = wins.len() as f64 / results.len().max(1) as f64 * 100.0;
+    let average = wins.iter().map(|r| r.1).sum::<usize>() as f64 / wins.len().max(1) as f64;
+
+    println!("opener      {opener}");
+    println!("words       {}", results.len());
+    println!(
+        "win rate    {win_rate:.1}% ({}/{})",
+        wins.len(),
+        results.len()
+    );
+    println!("average     {average:.2} guesses");
+
+    results.sort_by_key(|r| (r.2, Reverse(r.1)));
+    println!("worst cases");
+    for (word, attempts, solved) in results.iter().take(10) {
+        let outcome = if *solved { "" } else { " (failed)" };
+        println!("  {word}  {attempts}{outcome}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the statuses of the guess as `G`, `Y` and `-`, like `--play-from` prints them.
+    fn pattern(input: &str, word: &str) -> String {
+        get_spots(input, word)
+            .iter()
+            .map(|s| match s.status {
+                LetterStatus::Correct => 'G',
+                LetterStatus::Incorrect => 'Y',
+                LetterStatus::NotInWord => '-',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn marks_letters_by_position() {
+        assert_eq!(pattern("CRANE", "SLATE"), "--G-G");
+        assert_eq!(pattern("STALE", "SLATE"), "GYGYG");
+    }
+
+    #[test]
+    fn marks_repeated_letters_only_as_often_as_the_word_has_them() {
+        assert_eq!(pattern("EERIE", "ENSUE"), "G---G");
+        assert_eq!(pattern("EERIE", "SLATE"), "----G");
+        assert_eq!(pattern("SPEED", "ABIDE"), "--Y-Y");
+        assert_eq!(pattern("ERASE", "SPEED"), "Y--YY");
+    }
+
+    #[test]
+    fn candidates_must_match_every_status() {
+        let guess = get_spots("EERIE", "ENSUE");
+        assert!(is_consistent("ENSUE", &guess));
+        assert!(!is_consistent("EERIE", &guess));
+    }
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn largest_partition_keeps_the_most_candidates() {
+        let candidates = words(&["SLATE", "PLATE", "CRATE", "PIOUS"]);
+        let kept = largest_partition("CRANE", &candidates);
+        assert_eq!(kept, words(&["SLATE", "PLATE"]));
+        assert_eq!(pattern("CRANE", "SLATE"), pattern("CRANE", "PLATE"));
+    }
+
+    #[test]
+    fn largest_partition_only_gives_up_the_guess_when_it_is_the_last_candidate() {
+        // every feedback leaves one candidate, so the one revealing the least is given
+        let candidates = words(&["CRANE", "PIOUS"]);
+        assert_eq!(largest_partition("CRANE", &candidates), words(&["PIOUS"]));
+        assert_eq!(
+            largest_partition("CRANE", &words(&["CRANE"])),
+            words(&["CRANE"])
+        );
+    }
+
+    #[test]
+    fn adversarial_answer_is_consistent_with_every_reveal() {
+        let mut candidates = words(&[
+            "CRANE", "SLATE", "PLATE", "CRATE", "ENSUE", "PIOUS", "SPEED", "ABIDE", "ERASE",
+        ]);
+        let mut reveals = Vec::new();
+        for guess in ["CRANE", "SLATE", "ABIDE", "PLATE", "ERASE", "SPEED"] {
+            candidates = largest_partition(guess, &candidates);
+            reveals.push(get_spots(guess, &candidates[0]));
+        }
+
+        // whichever candidate is left, it would have given every guess the same feedback
+        for candidate in &candidates {
+            assert!(reveals.iter().all(|r| is_consistent(candidate, r)));
+        }
+    }
+}
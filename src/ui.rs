@@ -1,16 +1,29 @@
-use std::collections::HashSet;
-use std::fmt::Write;
-use std::io;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, IsTerminal};
+use std::ops::Range;
+use std::time::{Duration, SystemTime};
 
 use arboard::Clipboard;
-use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode};
+use crossterm::event::{
+    DisableBracketedPaste,
+    DisableMouseCapture,
+    EnableBracketedPaste,
+    EnableMouseCapture,
+    Event,
+    KeyCode,
+    KeyEvent,
+    KeyEventKind,
+};
 use crossterm::execute;
+use crossterm::style::Print;
 use crossterm::terminal::{
     disable_raw_mode,
     enable_raw_mode,
     EnterAlternateScreen,
     LeaveAlternateScreen,
+    SetTitle,
 };
+use serde::{Deserialize, Serialize};
 use tui::backend::{Backend, CrosstermBackend};
 use tui::layout::{Alignment, Constraint, Direction, Layout};
 use tui::style::{Color, Modifier, Style};
@@ -18,8 +31,380 @@ use tui::text::{Span, Spans, Text};
 use tui::widgets::{Block, Borders, Paragraph, Wrap};
 use tui::{Frame, Terminal};
 
-use crate::error::Result;
-use crate::{LetterStatus, Spot, ALPHABETS};
+use crate::clock::Clock;
+use crate::error::{AppError, Result};
+use crate::events::{EventSource, TerminalEvents};
+use crate::i18n::{fill, Messages};
+use crate::random::{self, Rng};
+use crate::solver::{get_spots, is_consistent, largest_partition, remaining_after};
+use crate::{letter_to_index, GuessResult, LetterStatus, Spot, ALPHABETS};
+
+/// The color scheme used to display letter statuses.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Theme {
+    #[default]
+    Normal,
+    HighContrast,
+    Monochrome,
+}
+
+impl Theme {
+    /// Returns the theme that follows this one.
+    fn next(self) -> Self {
+        match self {
+            Self::Normal => Self::HighContrast,
+            Self::HighContrast => Self::Monochrome,
+            Self::Monochrome => Self::Normal,
+        }
+    }
+
+    /// Returns the theme with the given name, like `high-contrast`, ignoring case.
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "normal" => Some(Self::Normal),
+            "high-contrast" => Some(Self::HighContrast),
+            "monochrome" => Some(Self::Monochrome),
+            _ => None,
+        }
+    }
+
+    /// Returns the display name of the theme.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::HighContrast => "High contrast",
+            Self::Monochrome => "Monochrome",
+        }
+    }
+}
+
+/// How the word of the game was picked.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    /// The next word of the list.
+    #[default]
+    Sequential,
+    /// A word picked with `--puzzle`.
+    Replay,
+    /// A word picked with `--from-code`.
+    Challenge,
+    /// A random word favouring the player's weak letters.
+    PracticeWeak,
+    /// A random word played with `--unlimited`.
+    Practice,
+    /// No word until the game has to settle on one.
+    Adversarial,
+    /// A word player 1 typed.
+    TwoPlayer,
+}
+
+impl GameMode {
+    /// Returns the name of the mode, as used in `--json`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sequential => "sequential",
+            Self::Replay => "replay",
+            Self::Challenge => "challenge",
+            Self::PracticeWeak => "practice-weak",
+            Self::Practice => "practice",
+            Self::Adversarial => "adversarial",
+            Self::TwoPlayer => "two-player",
+        }
+    }
+
+    /// Returns the 1-based puzzle number of the word at the index, or `None` when the answer
+    /// doesn't come from the word list.
+    pub fn puzzle_number(self, index: usize) -> Option<usize> {
+        match self {
+            Self::Adversarial | Self::TwoPlayer => None,
+            _ => Some(index + 1),
+        }
+    }
+
+    /// Returns the text shown below the board in place of the word's position in the list, or
+    /// `None` when the word is the next one in the list.
+    fn label(self, messages: &Messages) -> Option<&str> {
+        let label = match self {
+            Self::Sequential => return None,
+            Self::Replay => &messages.mode_replay,
+            Self::Challenge => &messages.mode_challenge,
+            Self::PracticeWeak => &messages.mode_practice_weak,
+            Self::Practice => &messages.mode_practice,
+            Self::Adversarial => &messages.mode_adversarial,
+            Self::TwoPlayer => &messages.mode_two_player,
+        };
+        Some(label)
+    }
+}
+
+/// Where the rows of the board are placed horizontally.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BoardAlignment {
+    Left,
+    #[default]
+    Center,
+}
+
+impl From<BoardAlignment> for Alignment {
+    fn from(alignment: BoardAlignment) -> Self {
+        match alignment {
+            BoardAlignment::Left => Alignment::Left,
+            BoardAlignment::Center => Alignment::Center,
+        }
+    }
+}
+
+/// Custom strings used in place of the theme's emoji in the shareable result.
+///
+/// Statuses without a custom string use the theme's emoji.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Emojis {
+    #[serde(default)]
+    pub correct: Option<String>,
+    #[serde(default)]
+    pub incorrect: Option<String>,
+    #[serde(default)]
+    pub not_in_word: Option<String>,
+}
+
+impl Emojis {
+    /// Returns the custom string for the given status, if one is set.
+    fn get(&self, status: LetterStatus) -> Option<&str> {
+        match status {
+            LetterStatus::Correct => self.correct.as_deref(),
+            LetterStatus::Incorrect => self.incorrect.as_deref(),
+            LetterStatus::NotInWord => self.not_in_word.as_deref(),
+        }
+    }
+}
+
+/// Custom colors used in place of the theme's, each a name like `light-red` or a hex code like
+/// `#538d4e`.
+///
+/// Statuses without a custom color use the theme's color.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Colors {
+    #[serde(default)]
+    pub correct: Option<String>,
+    #[serde(default)]
+    pub incorrect: Option<String>,
+    #[serde(default)]
+    pub not_in_word: Option<String>,
+    #[serde(default)]
+    pub border: Option<String>,
+}
+
+impl Colors {
+    /// Parses the custom colors, calling `invalid` with the name and value of each color that
+    /// can't be parsed, which is left to the theme.
+    pub fn palette(&self, mut invalid: impl FnMut(&str, &str)) -> Palette {
+        let mut parse = |name, spec: &Option<String>| {
+            let spec = spec.as_deref()?;
+            let color = parse_color(spec);
+            if color.is_none() {
+                invalid(name, spec);
+            }
+            color
+        };
+
+        Palette {
+            correct: parse("correct", &self.correct),
+            incorrect: parse("incorrect", &self.incorrect),
+            not_in_word: parse("not_in_word", &self.not_in_word),
+            border: parse("border", &self.border),
+        }
+    }
+}
+
+/// The custom colors from [`Colors`] that could be parsed.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Palette {
+    pub correct: Option<Color>,
+    pub incorrect: Option<Color>,
+    pub not_in_word: Option<Color>,
+    pub border: Option<Color>,
+}
+
+impl Palette {
+    /// Returns the custom color for the given status, if one is set.
+    fn get(&self, status: LetterStatus) -> Option<Color> {
+        match status {
+            LetterStatus::Correct => self.correct,
+            LetterStatus::Incorrect => self.incorrect,
+            LetterStatus::NotInWord => self.not_in_word,
+        }
+    }
+}
+
+/// Parses a color name like `light-red` or a hex code like `#538d4e`, ignoring case.
+///
+/// Hex codes need a terminal with truecolor support to be shown exactly.
+pub fn parse_color(spec: &str) -> Option<Color> {
+    let spec = spec.trim().to_ascii_lowercase();
+    if let Some(hex) = spec.strip_prefix('#') {
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+
+    let color = match spec.replace('_', "-").as_str() {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "dark-gray" | "dark-grey" => Color::DarkGray,
+        "light-red" => Color::LightRed,
+        "light-green" => Color::LightGreen,
+        "light-yellow" => Color::LightYellow,
+        "light-blue" => Color::LightBlue,
+        "light-magenta" => Color::LightMagenta,
+        "light-cyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// The word to guess and the context it was selected in.
+pub struct Puzzle {
+    pub word: String,
+    /// The answer as it should be shown, which may keep its casing from the word list.
+    pub display_word: String,
+    pub allowed_guesses: HashSet<String>,
+    /// The index of the word in the word list.
+    pub index: usize,
+    /// The number of words of the list played before this one, in the order they're played.
+    pub played: usize,
+    /// The number of words in the word list.
+    pub total: usize,
+    /// The number of guesses the player gets to find the word.
+    pub max_attempts: usize,
+    /// The number of guesses the word took when it was last solved, if it was.
+    pub previous_attempts: Option<usize>,
+    /// The possible answers, used to show how many remained after each guess.
+    pub candidates: Vec<String>,
+}
+
+/// The number of letters in a word.
+pub const WORD_LENGTH: usize = 5;
+
+/// How long the input row stays highlighted after a guess is rejected.
+const SHAKE_DURATION: Duration = Duration::from_millis(300);
+
+/// How long after a guess is submitted, or the game ends, that another press of enter is ignored.
+///
+/// Holding a key down sends it repeatedly, which would otherwise submit the next guess or skip
+/// the result screen by accident.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Saves the window title on the terminal's title stack, so it can be restored after the game.
+///
+/// Terminals without a title stack ignore this, and keep the game's title after it ends.
+const PUSH_TITLE: &str = "\x1b[22;0t";
+
+/// Restores the window title saved with [`PUSH_TITLE`].
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// The smallest terminal size, in columns and rows, that the board can be drawn in.
+const MIN_SIZE: (u16, u16) = (20, 14);
+
+/// An action on the result screen menu.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum MenuItem {
+    Copy,
+    CopyBoard,
+    Quit,
+}
+
+impl MenuItem {
+    fn label(self, messages: &Messages) -> &str {
+        match self {
+            Self::Copy => &messages.menu_copy,
+            Self::CopyBoard => &messages.menu_copy_board,
+            Self::Quit => &messages.menu_quit,
+        }
+    }
+}
+
+/// How a game ended.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum GameOutcome {
+    /// The word was found, with the number of guesses it took.
+    Win(usize),
+    /// Every guess was used without finding the word.
+    Loss,
+}
+
+/// The actions shown on the result screen, in order.
+const MENU: [MenuItem; 3] = [MenuItem::Copy, MenuItem::CopyBoard, MenuItem::Quit];
+
+/// Settings that change how the game is played or displayed.
+#[derive(Clone, Debug, Default)]
+pub struct Settings {
+    pub emojis: Emojis,
+    pub colors: Palette,
+    pub messages: Messages,
+    /// A word to suggest as the first guess.
+    pub suggestion: Option<String>,
+    /// Whether letter statuses are labelled with text so they don't rely on color.
+    pub a11y: bool,
+    /// Whether to suggest the closest allowed word when a guess is rejected.
+    pub did_you_mean: bool,
+    /// Whether to show hints deduced from previous guesses.
+    pub assist: bool,
+    /// Whether to hide the alphabet panel below the guesses.
+    pub hide_keyboard: bool,
+    /// Whether the shareable result is a single line instead of a grid.
+    pub compact_share: bool,
+    /// Whether to show how many possible answers remained after each guess.
+    pub analyze: bool,
+    /// Whether to show statuses with text styles instead of colors.
+    pub no_color: bool,
+    /// Whether player 1 types the answer for player 2 before the game starts.
+    pub two_player: bool,
+    /// Flavor text shown on a win, by the number of guesses it took, replacing the defaults.
+    pub win_messages: BTreeMap<usize, String>,
+    /// Whether the color legend is shown when the game starts.
+    pub show_legend: bool,
+    /// The number of letters that can be revealed to not be in the word.
+    pub gray_hints: usize,
+    /// Where the rows of the board are placed.
+    pub alignment: BoardAlignment,
+    /// The number of spaces between tiles.
+    pub tile_spacing: usize,
+    /// Whether a guess is submitted as soon as its last letter is typed.
+    pub auto_submit: bool,
+    /// Whether to skip transient effects, like highlighting a rejected guess.
+    pub no_animation: bool,
+    /// Whether guessing continues until the word is found, ignoring the attempt limit.
+    pub unlimited: bool,
+    /// A word played as the first guess before the player takes over.
+    pub opener: Option<String>,
+    /// Whether to ring the terminal bell when the game ends.
+    pub notify: bool,
+    /// Whether to leave the mouse to the terminal, so text can be selected with it.
+    pub no_mouse: bool,
+    /// Whether letters known to be in the right place are shown faintly in the input row.
+    pub sticky_greens: bool,
+    /// Whether the answer is picked as late as possible, to be the hardest one to find.
+    pub adversarial: bool,
+    /// Whether to show the puzzle number in the terminal's window title during the game.
+    pub title: bool,
+    /// A code to play the same word, shown on the result screen.
+    pub share_code: Option<String>,
+    /// How the word was picked. How far through the word list the game is only makes sense
+    /// for the next word of the list, so it's shown only then.
+    pub mode: GameMode,
+    /// Whether to show how many guesses are left in the footer.
+    pub show_remaining: bool,
+}
 
 /// App holds the state of the application
 struct App {
@@ -27,150 +412,716 @@ struct App {
     message: Option<String>,
     guesses: Vec<[Spot; 5]>,
     alphabet_statuses: [Option<LetterStatus>; 26],
+    /// Letters known to be in the word but not at each position.
+    known_not_here: [HashSet<char>; 5],
     attempts: usize,
+    max_attempts: usize,
     word: String,
+    display_word: String,
     allowed_guesses: HashSet<String>,
     index: usize,
+    played: usize,
+    total: usize,
+    previous_attempts: Option<usize>,
+    theme: Theme,
+    settings: Settings,
+    started: SystemTime,
+    duration: Option<Duration>,
+    /// The selected action on the result screen menu.
+    menu_index: usize,
+    /// When the highlight of a rejected guess ends, if one is showing.
+    shake_until: Option<SystemTime>,
+    /// The possible answers that are consistent with every guess so far.
+    candidates: Vec<String>,
+    /// The number of possible answers left after each guess.
+    remaining: Vec<usize>,
+    /// Whether player 1 is still typing the answer.
+    entering_secret: bool,
+    /// Whether the color legend is shown.
+    show_legend: bool,
+    /// How the game ended, once it has.
+    outcome: Option<GameOutcome>,
+    /// The number of letters revealed to not be in the word.
+    hints_used: usize,
+    /// When enter was last pressed to submit the input.
+    last_submit: Option<SystemTime>,
+    /// The command being typed, while the command palette is open.
+    palette: Option<String>,
+    /// The letters locked in each position, which fill the input of every row.
+    locked: [Option<char>; WORD_LENGTH],
 }
 
 impl App {
-    fn new(word: String, allowed_guesses: HashSet<String>, index: usize) -> Self {
+    fn new(puzzle: Puzzle, settings: Settings, theme: Theme, started: SystemTime) -> Self {
+        let Puzzle {
+            word,
+            display_word,
+            allowed_guesses,
+            index,
+            played,
+            total,
+            max_attempts,
+            previous_attempts,
+            candidates,
+        } = puzzle;
+
         Self {
             input: String::new(),
-            message: None,
+            message: if settings.two_player {
+                None
+            } else {
+                suggestion_message(&settings)
+            },
             guesses: Vec::new(),
             alphabet_statuses: [None; 26],
+            known_not_here: Default::default(),
             attempts: 0,
+            max_attempts,
             word,
+            display_word,
             allowed_guesses,
             index,
+            played,
+            total,
+            previous_attempts,
+            theme,
+            started,
+            duration: None,
+            // enter leaves the result screen, like any key did before there was a menu
+            menu_index: MENU.len() - 1,
+            shake_until: None,
+            candidates,
+            remaining: Vec::new(),
+            entering_secret: settings.two_player,
+            show_legend: settings.show_legend,
+            outcome: None,
+            hints_used: 0,
+            last_submit: None,
+            palette: None,
+            locked: [None; WORD_LENGTH],
+            settings,
+        }
+    }
+
+    /// Adds a letter to the input, ignoring anything that isn't a letter or would make the
+    /// input longer than the word.
+    fn push_input(&mut self, c: char) {
+        if c.is_ascii_alphabetic() && self.input.len() < WORD_LENGTH {
+            self.input.push(c.to_ascii_uppercase());
+            self.fill_locked();
+        }
+    }
+
+    /// Removes the last letter typed, along with any locked letters after it.
+    fn pop_input(&mut self) {
+        let typed = (0..self.input.len())
+            .rev()
+            .find(|&i| self.locked[i].is_none());
+        if let Some(position) = typed {
+            self.input.truncate(position);
+            self.fill_locked();
+        }
+    }
+
+    /// Adds the locked letters that come next in the input, so they don't have to be typed.
+    fn fill_locked(&mut self) {
+        while let Some(Some(letter)) = self.locked.get(self.input.len()) {
+            self.input.push(*letter);
+        }
+    }
+
+    /// Locks the last letter of the input in its position for the rest of the game, or unlocks
+    /// it if it's locked.
+    fn toggle_lock(&mut self) {
+        if let Some(letter) = self.input.chars().last() {
+            let lock = &mut self.locked[self.input.len() - 1];
+            *lock = if lock.is_some() { None } else { Some(letter) };
+        }
+    }
+
+    /// Shows why the input was rejected and briefly highlights it.
+    fn reject<C: Clock>(&mut self, message: String, clock: &C) {
+        self.message = Some(message);
+        if !self.settings.no_animation {
+            self.shake_until = Some(clock.now() + SHAKE_DURATION);
+        }
+    }
+
+    /// Scores the input as a guess, or shows why it can't be guessed.
+    fn submit_guess<C: Clock>(&mut self, clock: &C) {
+        if self.input.len() != WORD_LENGTH || !self.allowed_guesses.contains(&self.input) {
+            let suggestion = if self.settings.did_you_mean {
+                closest_word(&self.input, &self.allowed_guesses)
+            } else {
+                None
+            };
+            let message = match suggestion {
+                Some(word) => fill(&self.settings.messages.did_you_mean, &[("word", &word)]),
+                None => self.settings.messages.invalid_guess.clone(),
+            };
+            self.reject(message, clock);
+            return;
+        }
+
+        self.message = None;
+
+        // the answer is whichever candidate keeps the most possible answers after this guess
+        if self.settings.adversarial {
+            self.candidates = largest_partition(&self.input, &self.candidates);
+            if let Some(word) = self.candidates.first() {
+                self.word = word.clone();
+                self.display_word = word.clone();
+            }
+        }
+
+        let spots = get_spots(&self.input, &self.word);
+        self.guesses.push(spots);
+        self.attempts += 1;
+        if self.settings.analyze || self.settings.assist {
+            self.candidates.retain(|c| is_consistent(c, &spots));
+        }
+        if self.settings.analyze {
+            self.remaining.push(self.candidates.len());
+        }
+
+        if self.input == self.word {
+            self.end(GameOutcome::Win(self.attempts), clock);
+            return;
+        }
+
+        if !self.settings.unlimited && self.attempts == self.max_attempts {
+            self.end(GameOutcome::Loss, clock);
+        }
+
+        for (position, spot) in spots.iter().enumerate() {
+            // a repeated letter can be gray in one place and green in another, so keep the best
+            let status =
+                &mut self.alphabet_statuses[letter_to_index(spot.letter).unwrap_or_default()];
+            *status = Some(status.map_or(spot.status, |s| s.min(spot.status)));
+            if let LetterStatus::Incorrect = spot.status {
+                self.known_not_here[position].insert(spot.letter);
+            }
+        }
+
+        self.input.clear();
+        self.fill_locked();
+    }
+
+    /// Reveals a random letter that isn't in the word and hasn't been ruled out yet, if any hints
+    /// are left.
+    fn reveal_gray<C: Clock>(&mut self, clock: &C) {
+        let messages = &self.settings.messages;
+        let remaining = self.settings.gray_hints.saturating_sub(self.hints_used);
+        let absent: Vec<_> = (0..26)
+            .filter(|&i| self.alphabet_statuses[i].is_none() && !self.word.contains(ALPHABETS[i]))
+            .collect();
+        if remaining == 0 || absent.is_empty() {
+            self.message = Some(messages.no_hints.clone());
+            return;
+        }
+
+        let index = absent[Rng::new(random::new_seed(clock)).below(absent.len())];
+        self.alphabet_statuses[index] = Some(LetterStatus::NotInWord);
+        self.hints_used += 1;
+        self.message = Some(fill(&messages.gray_hint, &[
+            ("letter", &ALPHABETS[index]),
+            ("count", &(remaining - 1)),
+        ]));
+    }
+
+    /// Makes the input the answer, if it's an allowed word, and starts the game.
+    fn submit_secret<C: Clock>(&mut self, clock: &C) {
+        if self.input.len() != WORD_LENGTH || !self.allowed_guesses.contains(&self.input) {
+            self.reject(self.settings.messages.invalid_guess.clone(), clock);
+            return;
+        }
+
+        self.word = std::mem::take(&mut self.input);
+        self.display_word = self.word.clone();
+        self.entering_secret = false;
+        self.message = suggestion_message(&self.settings);
+        self.started = clock.now();
+    }
+
+    /// Returns the flavor text for a win that took the given number of guesses.
+    fn win_flavor(&self, attempts: usize) -> &str {
+        let messages = &self.settings.messages;
+        self.settings
+            .win_messages
+            .get(&attempts)
+            .or_else(|| messages.win_flavors.get(attempts.wrapping_sub(1)))
+            .unwrap_or(&messages.win_fallback)
+    }
+
+    /// Runs a command typed in the command palette, returning whether the game should be quit.
+    fn run_command<C: Clock>(&mut self, command: &str, clock: &C) -> bool {
+        let mut words = command.split_whitespace();
+        match (words.next(), words.next()) {
+            (Some("q" | "quit"), None) => return true,
+            (Some("hint"), None) => self.reveal_gray(clock),
+            (Some("legend"), None) => self.show_legend = !self.show_legend,
+            (Some("give-up"), None) => self.end(GameOutcome::Loss, clock),
+            (Some("theme"), name) => match name.map_or(Some(self.theme.next()), Theme::from_name) {
+                Some(theme) => {
+                    self.theme = theme;
+                    self.message = Some(fill(&self.settings.messages.theme_changed, &[(
+                        "theme",
+                        &theme.name(),
+                    )]));
+                },
+                None => self.unknown_command(command),
+            },
+            _ => self.unknown_command(command),
+        }
+        false
+    }
+
+    fn unknown_command(&mut self, command: &str) {
+        self.message = Some(fill(&self.settings.messages.unknown_command, &[(
+            "command",
+            &command.trim(),
+        )]));
+    }
+
+    /// Returns whether the key is likely a held key repeating rather than a deliberate press.
+    ///
+    /// Terminals that report key repeats have them ignored for enter and on the result screen.
+    /// Other terminals get the same effect from ignoring enter shortly after the last submission
+    /// and any key shortly after the game ends. Typing letters is never held back.
+    fn is_repeat<C: Clock>(&self, key: &KeyEvent, clock: &C) -> bool {
+        let now = clock.now();
+        let within_debounce =
+            |since: SystemTime| now.duration_since(since).map_or(true, |d| d < DEBOUNCE);
+
+        if self.outcome.is_some() {
+            let ended = self.started + self.duration.unwrap_or_default();
+            key.kind == KeyEventKind::Repeat || within_debounce(ended)
+        } else if key.code == KeyCode::Enter {
+            key.kind == KeyEventKind::Repeat || self.last_submit.is_some_and(within_debounce)
+        } else {
+            false
+        }
+    }
+
+    /// Ends the game with the given outcome.
+    fn end<C: Clock>(&mut self, outcome: GameOutcome, clock: &C) {
+        self.outcome = Some(outcome);
+        self.finish(clock);
+    }
+
+    /// Records how long the game took, if it has not been recorded yet.
+    fn finish<C: Clock>(&mut self, clock: &C) {
+        if self.duration.is_none() {
+            self.duration = Some(clock.now().duration_since(self.started).unwrap_or_default());
         }
     }
+
+    /// Returns a style with the foreground color, unless colors are disabled.
+    fn fg(&self, color: Color) -> Style {
+        if self.settings.no_color {
+            Style::default()
+        } else {
+            Style::default().fg(color)
+        }
+    }
+
+    /// Returns the style of the borders around the board.
+    fn border_style(&self) -> Style {
+        self.fg(self.settings.colors.border.unwrap_or(Color::Yellow))
+    }
+
+    /// Returns the style of a letter with the given status.
+    ///
+    /// Without colors, statuses are told apart by text styles instead.
+    fn status_style(&self, status: LetterStatus) -> Style {
+        if !self.settings.no_color {
+            return Style::default().fg(color_from_status(
+                status,
+                self.theme,
+                &self.settings.colors,
+            ));
+        }
+
+        let modifier = match status {
+            LetterStatus::Correct => Modifier::BOLD | Modifier::UNDERLINED,
+            LetterStatus::Incorrect => Modifier::UNDERLINED,
+            LetterStatus::NotInWord => Modifier::DIM,
+        };
+        Style::default().add_modifier(modifier)
+    }
+
+    /// Returns the result of the game.
+    fn result(&self) -> GuessResult {
+        GuessResult {
+            word: self.word.clone(),
+            guesses: self.guesses.iter().map(|g| g.to_vec()).collect(),
+            max_attempts: self.max_attempts,
+            duration: self.duration.unwrap_or_default(),
+            hints: self.hints_used,
+            practice: self.settings.unlimited,
+            // a loss with guesses left can only come from giving up
+            gave_up: self.outcome == Some(GameOutcome::Loss)
+                && self
+                    .attempt_limit()
+                    .is_none_or(|limit| self.attempts < limit),
+        }
+    }
+
+    /// Returns the number of guesses allowed, or `None` when there is no limit.
+    fn attempt_limit(&self) -> Option<usize> {
+        (!self.settings.unlimited).then_some(self.max_attempts)
+    }
 }
 
-pub fn main(word: String, allowed_guesses: HashSet<String>, index: usize) -> Result<()> {
-    // setup terminal
-    enable_raw_mode()?;
+/// Runs the game and returns its result.
+///
+/// `theme` is updated with the theme selected when the game ended.
+pub fn main<C: Clock>(
+    puzzle: Puzzle,
+    settings: Settings,
+    theme: &mut Theme,
+    clock: &C,
+) -> Result<GuessResult> {
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    // the game would be written into a pipe or file, which nobody can play
+    if !stdout.is_terminal() {
+        return Err(AppError::NoTerminal);
+    }
+
+    // setup terminal
+    // restricted terminals fail here, which is better reported as the game being unavailable
+    // than as the raw error
+    let setup = enable_raw_mode()
+        .and_then(|_| execute!(stdout, EnterAlternateScreen, EnableBracketedPaste));
+    if setup.is_err() {
+        let _ = disable_raw_mode();
+        return Err(AppError::NoTerminal);
+    }
+    // capturing the mouse stops the terminal from selecting text with it
+    let capture_mouse = !settings.no_mouse;
+    if capture_mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
+    let set_title = settings.title;
+    if set_title {
+        let title = match settings.mode {
+            GameMode::TwoPlayer => "wordle-cli — Two players".to_string(),
+            GameMode::Adversarial => "wordle-cli — Adversarial".to_string(),
+            _ => format!("wordle-cli — Puzzle #{}", puzzle.index + 1),
+        };
+        execute!(stdout, Print(PUSH_TITLE), SetTitle(title))?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.autoresize()?;
 
     // create app and run it
-    let app = App::new(word, allowed_guesses, index);
-    let res = run_app(&mut terminal, app);
+    let mut app = App::new(puzzle, settings, *theme, clock.now());
+    if let Some(opener) = app.settings.opener.clone() {
+        app.input = opener;
+        app.submit_guess(clock);
+    }
+    let res = run_app(&mut terminal, &mut app, clock, &mut TerminalEvents);
 
     // restore terminal
     disable_raw_mode()?;
+    if capture_mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    if set_title {
+        execute!(terminal.backend_mut(), Print(POP_TITLE))?;
+    }
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture,
+        DisableBracketedPaste,
     )?;
     terminal.show_cursor()?;
 
-    res
+    *theme = app.theme;
+    app.finish(clock);
+
+    res.map(|_| app.result())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> Result<()> {
-    let mut win = false;
+fn run_app<B: Backend, C: Clock, E: EventSource>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    clock: &C,
+    events: &mut E,
+) -> Result<()> {
     terminal.show_cursor()?;
+    let mut notified = false;
     loop {
+        // ring once when the game ends, not on every redraw of the result screen
+        if app.settings.notify && app.outcome.is_some() && !notified {
+            execute!(io::stdout(), Print('\x07'))?;
+            notified = true;
+        }
+
         terminal.draw(|f| {
-            if win {
-                success_ui(f, &app);
-            } else if app.attempts == 6 {
-                loss_ui(f, &app);
-            } else {
-                game_ui(f, &app);
+            if f.size().width < MIN_SIZE.0 || f.size().height < MIN_SIZE.1 {
+                too_small_ui(f, app);
+                return;
             }
-        })?;
 
-        if let Event::Key(key) = event::read()? {
-            if app.attempts == 6 || win {
-                if let KeyCode::Char('c') = key.code {
-                    let mut text = String::new();
-                    let los = result_text_spans(&app);
-                    for (i, spans) in los.iter().enumerate() {
-                        for span in &spans.0 {
-                            write!(&mut text, "{}", span.content)?;
-                        }
-                        writeln!(&mut text)?;
-                        if i == 0 {
-                            writeln!(&mut text)?;
-                        }
-                    }
-                    let mut clipboard = Clipboard::new()?;
-                    clipboard.set_text(text)?;
-                }
-                return Ok(());
+            match app.outcome {
+                Some(GameOutcome::Win(attempts)) => success_ui(f, app, attempts),
+                Some(GameOutcome::Loss) => loss_ui(f, app),
+                None if app.entering_secret => secret_ui(f, app),
+                None => game_ui(f, app),
             }
-            match key.code {
-                KeyCode::Enter => {
-                    if app.input.len() != 5 || !app.allowed_guesses.contains(&app.input) {
-                        app.message =
-                            Some("Not a valid five letter word. Try again... ".to_string());
-                        continue;
-                    }
-
-                    app.message = None;
+        })?;
 
-                    let spots = get_spots(&app.input, &app.word);
-                    app.guesses.push(spots);
-                    app.attempts += 1;
+        // wait for input only until the current animation ends, so it can be cleared
+        if let Some(until) = app.shake_until {
+            let remaining = until.duration_since(clock.now()).unwrap_or_default();
+            if !events.poll(remaining)? {
+                app.shake_until = None;
+                continue;
+            }
+        }
 
-                    if app.input == app.word {
-                        win = true;
-                        continue;
+        let key = match events.read()? {
+            Event::Key(key) => key,
+            Event::Resize(..) => {
+                terminal.autoresize()?;
+                continue;
+            },
+            Event::Paste(text) => {
+                if app.outcome.is_none() {
+                    for c in text.chars() {
+                        app.push_input(c);
                     }
+                }
+                continue;
+            },
+            _ => continue,
+        };
 
-                    for spot in spots {
-                        app.alphabet_statuses[letter_to_index(spot.letter).unwrap_or_default()] =
-                            Some(spot.status);
-                    }
+        if app.is_repeat(&key, clock) {
+            continue;
+        }
+        if key.code == KeyCode::Enter {
+            app.last_submit = Some(clock.now());
+        }
+
+        if app.entering_secret {
+            match key.code {
+                KeyCode::Enter => app.submit_secret(clock),
+                KeyCode::Char(c) => app.push_input(c),
+                KeyCode::Backspace => app.pop_input(),
+                KeyCode::Esc => return Ok(()),
+                _ => {},
+            }
+            continue;
+        }
 
-                    app.input.clear();
+        if app.outcome.is_some() {
+            match key.code {
+                KeyCode::Up | KeyCode::Left | KeyCode::Char('k') => {
+                    app.menu_index = app.menu_index.saturating_sub(1);
+                },
+                KeyCode::Down | KeyCode::Right | KeyCode::Char('j') => {
+                    app.menu_index = (app.menu_index + 1).min(MENU.len() - 1);
                 },
-                KeyCode::Char(c) => {
-                    app.input.push(c.to_ascii_uppercase());
+                KeyCode::Enter => match MENU[app.menu_index] {
+                    MenuItem::Copy => copy_result(app),
+                    MenuItem::CopyBoard => copy_board(app),
+                    MenuItem::Quit => return Ok(()),
+                },
+                KeyCode::Char('c') => copy_result(app),
+                KeyCode::Char('C') => copy_board(app),
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                _ => {},
+            }
+            continue;
+        }
+        if let Some(command) = &mut app.palette {
+            match key.code {
+                KeyCode::Enter => {
+                    let command = app.palette.take().unwrap_or_default();
+                    if app.run_command(&command, clock) {
+                        return Ok(());
+                    }
                 },
+                KeyCode::Char(c) => command.push(c),
+                KeyCode::Backspace if command.is_empty() => app.palette = None,
                 KeyCode::Backspace => {
-                    app.input.pop();
+                    command.pop();
                 },
-                KeyCode::Esc => return Ok(()),
+                KeyCode::Esc => app.palette = None,
                 _ => {},
             }
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => app.submit_guess(clock),
+            KeyCode::Char(':') => app.palette = Some(String::new()),
+            KeyCode::Char('?') => app.show_legend = !app.show_legend,
+            KeyCode::Char('!') if app.settings.gray_hints > 0 => app.reveal_gray(clock),
+            KeyCode::Char('#') if app.settings.assist => app.toggle_lock(),
+            KeyCode::Char(c) => {
+                let was_full = app.input.len() == WORD_LENGTH;
+                app.push_input(c);
+                // a rejected guess stays until it's edited, instead of being resubmitted
+                if app.settings.auto_submit && !was_full && app.input.len() == WORD_LENGTH {
+                    app.submit_guess(clock);
+                }
+            },
+            KeyCode::Backspace => app.pop_input(),
+            KeyCode::Tab => {
+                app.theme = app.theme.next();
+                app.message = Some(fill(&app.settings.messages.theme_changed, &[(
+                    "theme",
+                    &app.theme.name(),
+                )]));
+            },
+            KeyCode::Esc => return Ok(()),
+            _ => {},
+        }
+    }
+}
+
+/// Returns a submitted guess with each letter colored by its status.
+fn guess_spans<'a>(guess: &[Spot], app: &App) -> Spans<'a> {
+    let tiles = guess.iter().map(|spot| {
+        let tile = if app.settings.a11y {
+            format!("{}{} ", spot.letter, symbol_from_status(spot.status))
+        } else {
+            spot.letter.to_string()
+        };
+        Span::styled(tile, app.status_style(spot.status))
+    });
+    spaced_tiles(tiles, app.settings.tile_spacing)
+}
+
+/// Returns the tiles of a row with the given number of spaces between them.
+fn spaced_tiles<'a>(tiles: impl IntoIterator<Item = Span<'a>>, spacing: usize) -> Spans<'a> {
+    let mut spans = Vec::new();
+    for (i, tile) in tiles.into_iter().enumerate() {
+        if i > 0 && spacing > 0 {
+            spans.push(Span::raw(" ".repeat(spacing)));
         }
+        spans.push(tile);
+    }
+    Spans::from(spans)
+}
+
+/// Returns the width of a row of the board, in columns.
+fn row_width(app: &App) -> usize {
+    let tile_width = if app.settings.a11y { 3 } else { 1 };
+    WORD_LENGTH * tile_width + (WORD_LENGTH - 1) * app.settings.tile_spacing
+}
+
+/// Returns the in-progress input row.
+///
+/// Empty slots are shown as blanks, with the slot the next letter goes in highlighted. The row is
+/// red while a rejected guess is highlighted. With assist enabled, letters already shown to be in
+/// the wrong position are highlighted too.
+fn input_spans(app: &App) -> Spans<'_> {
+    let style = if app.shake_until.is_some() && app.settings.no_color {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else if app.shake_until.is_some() {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default()
+    };
+
+    let mut spans: Vec<_> = app
+        .input
+        .chars()
+        .enumerate()
+        .map(|(position, letter)| {
+            let style = if app.settings.assist && app.known_not_here[position].contains(&letter) {
+                app.fg(Color::Red).add_modifier(Modifier::UNDERLINED)
+            } else if app.locked[position].is_some() {
+                style.add_modifier(Modifier::BOLD)
+            } else {
+                style
+            };
+            Span::styled(letter.to_string(), style)
+        })
+        .collect();
+
+    // letters already placed are shown faintly where nothing has been typed yet
+    let show_greens = app.settings.assist || app.settings.sticky_greens;
+    for position in app.input.len()..WORD_LENGTH {
+        let green = app
+            .guesses
+            .iter()
+            .map(|g| g[position])
+            .find(|s| s.status == LetterStatus::Correct)
+            .filter(|_| show_greens);
+        let (tile, style) = match green {
+            Some(spot) => (spot.letter.to_string(), style.add_modifier(Modifier::DIM)),
+            None => ("_".to_string(), style),
+        };
+        let style = if position == app.input.len() {
+            style.add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK)
+        } else {
+            style
+        };
+        spans.push(Span::styled(tile, style));
     }
+    spaced_tiles(spans, app.settings.tile_spacing)
+}
+
+fn too_small_ui<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let widget = Paragraph::new(app.settings.messages.too_small.as_str())
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(widget, f.size());
 }
 
 fn game_ui<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let area = f.size();
+    // a row split over two lines would be misread, so ask for more room instead
+    // the margins and borders take up six columns
+    if (area.width.saturating_sub(6) as usize) < row_width(app) {
+        too_small_ui(f, app);
+        return;
+    }
+    let alphabet_rows = alphabet_rows(area.width.saturating_sub(4));
+    // the title, then the rows, then the found letters with assist enabled
+    let keyboard_height = alphabet_rows.len() as u16 + 1 + u16::from(app.settings.assist);
+
+    let help_height = if app.show_legend { 3 } else { 2 };
+
+    // hide the keyboard first when the terminal is too short to fit everything
+    // one row for each guess and the input, plus the borders
+    let guesses_height = (app.max_attempts as u16).saturating_add(2);
+    let required_height = (help_height + 1 + keyboard_height).saturating_add(guesses_height);
+    // with a large budget, the guesses get whatever is left and scroll
+    let available_height = area.height.saturating_sub(4 + help_height + 1);
+    let mut constraints = vec![
+        Constraint::Max(help_height),
+        Constraint::Length(guesses_height.min(available_height)),
+    ];
+    let show_keyboard =
+        !app.settings.hide_keyboard && area.height.saturating_sub(4) >= required_height;
+    if show_keyboard {
+        constraints.push(Constraint::Length(keyboard_height));
+    }
+    constraints.push(Constraint::Length(1));
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
-        .constraints(
-            [
-                Constraint::Max(2),
-                Constraint::Length(8),
-                Constraint::Length(3),
-            ]
-            .as_ref(),
-        )
-        .split(f.size());
+        .constraints(constraints)
+        .split(area);
 
-    let mut msg = vec![Spans::from(vec![
-        Span::raw("Press "),
-        Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(" to stop editing, "),
-        Span::styled("enter", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(" to submit a word."),
-    ])];
+    let mut msg = vec![controls_spans(&app.settings.messages.controls)];
+    if app.show_legend {
+        msg.push(legend_spans(app));
+    }
 
     if let Some(message) = &app.message {
+        msg.push(Spans::from(Span::styled(message, app.fg(Color::Red))));
+    } else if let Some(preview) = preview_message(app) {
         msg.push(Spans::from(Span::styled(
-            message,
-            Style::default().fg(Color::Red),
+            preview,
+            Style::default().add_modifier(Modifier::DIM),
         )));
     }
 
@@ -182,216 +1133,1087 @@ fn game_ui<B: Backend>(f: &mut Frame<B>, app: &App) {
     let mut text = app
         .guesses
         .iter()
-        .map(|g| {
-            let mut spans = Vec::with_capacity(5);
-            for spot in g {
-                spans.push(Span::styled(
-                    spot.letter.to_string(),
-                    Style::default().fg(color_from_status(spot.status)),
-                ));
-            }
-            Spans::from(spans)
-        })
+        .map(|g| guess_spans(g, app))
         .collect::<Vec<_>>();
-    text.push(Spans::from(Span::raw({
-        if app.input.is_empty() {
-            "_____"
-        } else {
-            &app.input
-        }
-    })));
+    text.push(input_spans(app));
+    // keep the input row, and the newest guesses above it, in view
+    let visible_rows = chunks[1].height.saturating_sub(2);
+    let scroll = (text.len() as u16).saturating_sub(visible_rows);
     let guesses_widget = Paragraph::new(text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow))
-                .title(format!("Guesses {}/6", app.attempts))
+                .border_style(app.border_style())
+                .title(fill(&app.settings.messages.guesses_title, &[
+                    ("attempts", &app.attempts),
+                    ("max", &limit_label(app.attempt_limit())),
+                ]))
                 .title_alignment(Alignment::Center),
         )
-        .alignment(Alignment::Center)
-        .wrap(Wrap { trim: true });
+        .alignment(app.settings.alignment.into())
+        .scroll((scroll, 0));
     f.render_widget(guesses_widget, chunks[1]);
 
-    f.render_widget(alphabets_widget(&app.alphabet_statuses), chunks[2]);
-}
+    if show_keyboard {
+        f.render_widget(alphabets_widget(app, &alphabet_rows), chunks[2]);
+    }
 
-fn color_from_status(status: LetterStatus) -> Color {
-    match status {
-        LetterStatus::Correct => Color::Green,
-        LetterStatus::Incorrect => Color::Yellow,
-        LetterStatus::NotInWord => Color::DarkGray,
+    let mut footer = Vec::new();
+    if let Some(command) = &app.palette {
+        // the palette takes over the footer, lined up on the left like a command line
+        let palette = Paragraph::new(format!(":{command}"));
+        f.render_widget(palette, chunks[chunks.len() - 1]);
+        return;
     }
+    if let (true, Some(limit)) = (app.settings.show_remaining, app.attempt_limit()) {
+        let remaining = limit.saturating_sub(app.attempts);
+        footer.push(Span::styled(
+            fill(&app.settings.messages.guesses_left, &[(
+                "count", &remaining,
+            )]),
+            app.fg(remaining_color(remaining, limit))
+                .add_modifier(Modifier::BOLD),
+        ));
+        footer.push(Span::raw(" "));
+    }
+    let label = app.settings.mode.label(&app.settings.messages);
+    if label.is_none() {
+        footer.push(Span::styled(
+            progress_bar(app.played, app.total),
+            app.fg(Color::Green),
+        ));
+        footer.push(Span::raw(" "));
+    }
+    let position = match label {
+        Some(label) => label.to_string(),
+        None => fill(&app.settings.messages.word_position, &[
+            ("index", &(app.played + 1)),
+            ("total", &app.total),
+        ]),
+    };
+    footer.push(Span::styled(
+        position,
+        Style::default().add_modifier(Modifier::DIM),
+    ));
+    let footer = Paragraph::new(Spans::from(footer)).alignment(Alignment::Center);
+    f.render_widget(footer, chunks[chunks.len() - 1]);
 }
 
-/// Returns the index of the given letter in the English alphabet.
-///
-/// Indexing starts at zero.
-///
-/// Returns [`None`] if the given letter is not present in the English alphabet.
-fn letter_to_index(letter: char) -> Option<usize> {
-    if letter.is_alphabetic() {
-        Some((letter.to_ascii_uppercase() as u8 - b'A') as usize)
+/// Returns the color of the remaining guesses: red on the last guess, yellow once a third or
+/// fewer are left, and green otherwise.
+fn remaining_color(remaining: usize, limit: usize) -> Color {
+    if remaining <= 1 {
+        Color::Red
+    } else if remaining * 3 <= limit {
+        Color::Yellow
     } else {
-        None
+        Color::Green
     }
 }
 
-fn alphabets_widget<'a>(alphabet_statuses: &[Option<LetterStatus>; 26]) -> Paragraph<'a> {
-    let mut spans = vec![Vec::new()];
-    for (index, status) in alphabet_statuses.iter().enumerate() {
-        let color = status.map_or(Color::Reset, color_from_status);
+/// Returns a bar showing how many of the words have been played, like `[####------]`.
+fn progress_bar(played: usize, total: usize) -> String {
+    const WIDTH: usize = 10;
+    let filled = (played * WIDTH)
+        .checked_div(total)
+        .unwrap_or_default()
+        .min(WIDTH);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(WIDTH - filled))
+}
 
-        spans.last_mut().unwrap().push(Span::styled(
-            ALPHABETS[index].to_string(),
-            Style::default().fg(color),
-        ));
+/// Shows the screen where player 1 types the answer, hiding the letters as they're typed.
+fn secret_ui<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Max(2),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(f.size());
 
-        if index != 23 && (index + 1) % 8 == 0 {
-            spans.push(Vec::new());
+    let mut msg = vec![controls_spans(&app.settings.messages.secret_prompt)];
+    if let Some(message) = &app.message {
+        msg.push(Spans::from(Span::styled(message, app.fg(Color::Red))));
+    }
+    f.render_widget(Paragraph::new(msg), chunks[0]);
+
+    let style = if app.shake_until.is_some() {
+        app.fg(Color::Red)
+    } else {
+        Style::default()
+    };
+    let masked = format!(
+        "{}{}",
+        "*".repeat(app.input.len()),
+        "_".repeat(WORD_LENGTH - app.input.len())
+    );
+    let widget = Paragraph::new(Span::styled(masked, style))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(app.border_style())
+                .title(app.settings.messages.secret_title.as_str())
+                .title_alignment(Alignment::Center),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(widget, chunks[1]);
+}
+
+/// Returns a line with a colored swatch for each status and what it means.
+fn legend_spans(app: &App) -> Spans<'_> {
+    let mut spans = Vec::new();
+    for status in [
+        LetterStatus::Correct,
+        LetterStatus::Incorrect,
+        LetterStatus::NotInWord,
+    ] {
+        if !spans.is_empty() {
+            spans.push(Span::raw("  "));
         }
+        spans.push(Span::styled("■", app.status_style(status)));
+        spans.push(Span::raw(format!(" {}", label_from_status(status))));
+    }
+    Spans::from(spans)
+}
+
+/// Returns the controls message, with text between asterisks in bold.
+fn controls_spans(controls: &str) -> Spans<'_> {
+    let spans: Vec<_> = controls
+        .split('*')
+        .enumerate()
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(i, text)| {
+            if i % 2 == 1 {
+                Span::styled(text, Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                Span::raw(text)
+            }
+        })
+        .collect();
+    Spans::from(spans)
+}
+
+/// Returns the message announcing the answer, with the `{word}` placeholder highlighted.
+fn answer_spans<'a>(message: &str, app: &'a App) -> Spans<'a> {
+    let (before, after) = message.split_once("{word}").unwrap_or((message, ""));
+    Spans::from(vec![
+        Span::raw(before.to_string()),
+        Span::styled(
+            &app.display_word,
+            app.fg(Color::Green).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(after.to_string()),
+    ])
+}
+
+/// Returns a line listing the letters known to be in the word, in alphabetical order.
+fn found_letters_spans(app: &App) -> Spans<'_> {
+    let found = found_letter_counts(&app.guesses);
+    if found.is_empty() {
+        return Spans::default();
+    }
+
+    // only letters known to appear more than once get a count, to keep the line short
+    let letters: Vec<_> = found
+        .iter()
+        .map(|(letter, &count)| match count {
+            1 => letter.to_string(),
+            _ => format!("{letter}×{count}"),
+        })
+        .collect();
+    Spans::from(Span::styled(
+        fill(&app.settings.messages.found_letters, &[(
+            "letters",
+            &letters.join(", "),
+        )]),
+        Style::default().add_modifier(Modifier::DIM),
+    ))
+}
+
+/// Returns how many possible answers a full input would leave if it was submitted, with assist
+/// enabled.
+///
+/// Only the possible answers are used, not the answer itself, so the preview is no spoiler.
+fn preview_message(app: &App) -> Option<String> {
+    if !app.settings.assist
+        || app.input.len() != WORD_LENGTH
+        || app.candidates.is_empty()
+        || !app.allowed_guesses.contains(&app.input)
+    {
+        return None;
+    }
+
+    let (expected, most) = remaining_after(&app.input, &app.candidates);
+    Some(fill(&app.settings.messages.preview, &[
+        ("word", &app.input),
+        ("expected", &format!("{expected:.1}")),
+        ("count", &app.candidates.len()),
+        ("most", &most),
+    ]))
+}
+
+/// Returns how many times each letter found so far is known to appear in the word.
+///
+/// A guess marks as many copies of a letter green or yellow as the word has, up to the number
+/// guessed, so the most copies marked in any one guess is how many are known.
+fn found_letter_counts(guesses: &[[Spot; 5]]) -> BTreeMap<char, usize> {
+    let mut found = BTreeMap::new();
+    for guess in guesses {
+        let mut counts = BTreeMap::new();
+        for spot in guess.iter().filter(|s| s.status != LetterStatus::NotInWord) {
+            *counts.entry(spot.letter).or_insert(0) += 1;
+        }
+        for (letter, count) in counts {
+            let known = found.entry(letter).or_insert(0);
+            *known = count.max(*known);
+        }
+    }
+    found
+}
+
+/// Returns a single character label of the status, used when colors can't be relied on.
+fn symbol_from_status(status: LetterStatus) -> char {
+    match status {
+        LetterStatus::Correct => 'G',
+        LetterStatus::Incorrect => 'Y',
+        LetterStatus::NotInWord => '-',
+    }
+}
+
+/// Returns the message suggesting a first guess, if there is a suggestion.
+fn suggestion_message(settings: &Settings) -> Option<String> {
+    settings
+        .suggestion
+        .as_ref()
+        .map(|w| fill(&settings.messages.suggestion, &[("word", w)]))
+}
+
+/// Returns a description of the status, used when colors can't be relied on.
+fn label_from_status(status: LetterStatus) -> &'static str {
+    match status {
+        LetterStatus::Correct => "correct",
+        LetterStatus::Incorrect => "present",
+        LetterStatus::NotInWord => "absent",
+    }
+}
+
+fn color_from_status(status: LetterStatus, theme: Theme, colors: &Palette) -> Color {
+    if let Some(color) = colors.get(status) {
+        return color;
+    }
+
+    match (theme, status) {
+        (Theme::Normal, LetterStatus::Correct) => Color::Green,
+        (Theme::Normal, LetterStatus::Incorrect) => Color::Yellow,
+        (Theme::HighContrast, LetterStatus::Correct) => Color::LightRed,
+        (Theme::HighContrast, LetterStatus::Incorrect) => Color::LightBlue,
+        (Theme::Monochrome, LetterStatus::Correct) => Color::White,
+        (Theme::Monochrome, LetterStatus::Incorrect) => Color::Gray,
+        (_, LetterStatus::NotInWord) => Color::DarkGray,
+    }
+}
+
+/// Splits the alphabet into rows that fit in the given width.
+///
+/// Rows hold eight letters, with the last two letters joining the third row, unless the width
+/// is too narrow for that.
+pub fn alphabet_rows(width: u16) -> Vec<Range<usize>> {
+    if width >= 10 {
+        return vec![0..8, 8..16, 16..26];
+    }
+
+    let width = width.max(1) as usize;
+    (0..26)
+        .step_by(width)
+        .map(|start| start..(start + width).min(26))
+        .collect()
+}
+
+fn alphabets_widget<'a>(app: &'a App, rows: &[Range<usize>]) -> Paragraph<'a> {
+    let mut spans = Vec::new();
+    for row in rows {
+        let mut letters = Vec::new();
+        for index in row.clone() {
+            let style =
+                app.alphabet_statuses[index].map_or(Style::default(), |s| app.status_style(s));
+            letters.push(Span::styled(ALPHABETS[index].to_string(), style));
+        }
+        spans.push(letters);
     }
 
     let mut text = Vec::new();
     for span in spans {
         text.push(Spans::from(span));
     }
+    if app.settings.assist {
+        text.push(found_letters_spans(app));
+    }
 
     Paragraph::new(text)
         .block(
             Block::default()
                 .borders(Borders::TOP)
-                .title("Alphabets")
+                .title(app.settings.messages.alphabet_title.as_str())
                 .title_alignment(Alignment::Center),
         )
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true })
 }
 
-fn success_ui<B: Backend>(f: &mut Frame<B>, app: &App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(2)
-        .constraints([Constraint::Min(8)].as_ref())
-        .split(f.size());
-
+fn success_ui<B: Backend>(f: &mut Frame<B>, app: &App, attempts: usize) {
     let mut spans = vec![
-        Spans::from(vec![
-            Span::raw("Correct! The word was "),
-            Span::styled(
-                &app.word,
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw("."),
-        ]),
-        Spans::from(Span::raw("")),
+        answer_spans(
+            &fill(&app.settings.messages.win, &[(
+                "flavor",
+                &app.win_flavor(attempts),
+            )]),
+            app,
+        ),
         Spans::from(Span::raw("")),
     ];
+    add_previous_attempts_spans(&mut spans, app);
 
-    spans.extend_from_slice(&result_text_spans(app));
-    add_copy_result_spans(&mut spans);
+    result_ui(f, app, &app.settings.messages.win_title, spans);
+}
 
-    let widget = Paragraph::new(spans)
-        .block(
-            Block::default()
-                .borders(Borders::TOP)
-                .title("RESULT")
-                .title_alignment(Alignment::Center),
-        )
-        .alignment(Alignment::Center)
-        .wrap(Wrap { trim: true });
+fn loss_ui<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let mut spans = vec![answer_spans(&app.settings.messages.loss, app)];
+    add_loss_note_spans(&mut spans, app);
+    spans.push(Spans::from(Span::raw("")));
+    add_previous_attempts_spans(&mut spans, app);
 
-    f.render_widget(widget, chunks[0]);
+    result_ui(f, app, &app.settings.messages.loss_title, spans);
 }
 
-fn loss_ui<B: Backend>(f: &mut Frame<B>, app: &App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
+/// Shows a result screen: the given lines and the shareable result, with the menu kept in view
+/// below them.
+///
+/// Lines that don't fit are cut off, ending with a line saying how many there were.
+fn result_ui<'a, B: Backend>(
+    f: &mut Frame<B>,
+    app: &'a App,
+    title: &'a str,
+    mut spans: Vec<Spans<'a>>,
+) {
+    let area = Layout::default()
         .margin(2)
-        .constraints([Constraint::Min(8)].as_ref())
-        .split(f.size());
+        .constraints([Constraint::Min(0)].as_ref())
+        .split(f.size())[0];
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .title(title)
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
 
-    let mut spans = vec![
-        Spans::from(vec![
-            Span::raw("The correct word was "),
-            Span::styled(
-                &app.word,
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw("."),
-        ]),
-        Spans::from(Span::raw("")),
-        Spans::from(Span::raw("")),
-    ];
+    let menu = menu_spans(app);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(menu.len() as u16)].as_ref())
+        .split(inner);
 
-    spans.extend_from_slice(&result_text_spans(app));
-    add_copy_result_spans(&mut spans);
+    spans.extend(result_text_spans(app));
+    if app.settings.a11y {
+        add_a11y_result_spans(&mut spans, app);
+    }
+    if app.settings.analyze {
+        add_analysis_spans(&mut spans, app);
+    }
+    // a large budget can leave more rows than fit, so say how many were left out
+    let height = chunks[0].height as usize;
+    if spans.len() > height {
+        let hidden = spans.len() + 1 - height;
+        spans.truncate(height.saturating_sub(1));
+        spans.push(Spans::from(Span::styled(
+            fill(&app.settings.messages.more_lines, &[("count", &hidden)]),
+            Style::default().add_modifier(Modifier::DIM),
+        )));
+    }
 
     let widget = Paragraph::new(spans)
-        .block(
-            Block::default()
-                .borders(Borders::TOP)
-                .title("Result!")
-                .title_alignment(Alignment::Center),
-        )
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true });
-
     f.render_widget(widget, chunks[0]);
+
+    let menu = Paragraph::new(menu).alignment(Alignment::Center);
+    f.render_widget(menu, chunks[1]);
+}
+
+/// Returns the number of guesses allowed for display, with `∞` when there is no limit.
+fn limit_label(limit: Option<usize>) -> String {
+    limit.map_or("∞".to_string(), |n| n.to_string())
 }
 
-fn result_text_spans(app: &App) -> Vec<Spans> {
-    let mut los = vec![Spans::from(Span::raw(format!(
-        "Wordle {} {}/6",
-        app.index + 1,
-        app.attempts
-    )))];
+/// Returns the header line of the shareable result.
+///
+/// Like the real game, a loss is shown as `X` instead of the number of attempts so it can't be
+/// mistaken for a win on the last guess. A game that hasn't ended shows the guesses so far.
+///
+/// `index` is the index of the word in the word list. A word player 1 typed or an adversarial
+/// answer has no puzzle number, so the mode is shown instead.
+pub fn result_header(mode: GameMode, index: usize, result: &GuessResult) -> String {
+    let puzzle = match mode {
+        GameMode::TwoPlayer => "(two player)".to_string(),
+        GameMode::Adversarial => "(adversarial)".to_string(),
+        _ => (index + 1).to_string(),
+    };
+    let limit = limit_label(result.attempt_limit());
+    if result.is_finished() && !result.is_win() {
+        format!("Wordle {puzzle} X/{limit}")
+    } else {
+        format!("Wordle {puzzle} {}/{limit}", result.guesses.len())
+    }
+}
+
+/// Returns the lines of the shareable result, exactly as they're copied, with each guess shown
+/// in color beside its row.
+///
+/// A compact result is a single line, so the colored guesses go above it instead.
+fn result_text_spans(app: &App) -> Vec<Spans<'_>> {
+    let text = build_share_text(app);
+    if app.settings.compact_share {
+        let mut spans: Vec<_> = app.guesses.iter().map(|g| guess_spans(g, app)).collect();
+        spans.push(Spans::from(Span::raw("")));
+        spans.push(Spans::from(Span::raw(text)));
+        return spans;
+    }
 
+    // the header and the blank line after it come before the rows
+    let mut guesses = app.guesses.iter();
+    text.lines()
+        .enumerate()
+        .map(
+            |(i, line)| match (i >= 2).then(|| guesses.next()).flatten() {
+                Some(guess) => {
+                    let mut row = guess_spans(guess, app).0;
+                    row.push(Span::raw(format!("   {line}")));
+                    Spans::from(row)
+                },
+                None => Spans::from(Span::raw(line.to_string())),
+            },
+        )
+        .collect()
+}
+
+/// Adds lines listing the letters that were never tried, and the letters that were found in the
+/// word but left out of a later guess.
+fn add_loss_note_spans(los: &mut Vec<Spans>, app: &App) {
+    let mut tried = [false; 26];
+    let mut found = [false; 26];
+    let mut left_out = [false; 26];
     for guess in &app.guesses {
-        let mut spans = Vec::new();
-        for spot in guess {
-            spans.push(Span::raw(emoji_from_status(spot.status)));
+        let letters: Vec<_> = guess
+            .iter()
+            .filter_map(|s| letter_to_index(s.letter))
+            .collect();
+        for index in 0..26 {
+            if found[index] && !letters.contains(&index) {
+                left_out[index] = true;
+            }
+        }
+        for (spot, &index) in guess.iter().zip(&letters) {
+            tried[index] = true;
+            found[index] |= spot.status != LetterStatus::NotInWord;
         }
-        los.push(Spans::from(spans));
     }
 
-    los
+    let list = |flags: [bool; 26]| {
+        let letters: Vec<_> = (0..26)
+            .filter(|&i| flags[i])
+            .map(|i| ALPHABETS[i].to_string())
+            .collect();
+        letters.join(", ")
+    };
+
+    let style = Style::default().add_modifier(Modifier::DIM);
+    let untried = list(tried.map(|t| !t));
+    if !untried.is_empty() {
+        los.push(Spans::from(Span::styled(
+            fill(&app.settings.messages.untried_letters, &[(
+                "letters", &untried,
+            )]),
+            style,
+        )));
+    }
+    let left_out = list(left_out);
+    if !left_out.is_empty() {
+        los.push(Spans::from(Span::styled(
+            fill(&app.settings.messages.unused_hints, &[(
+                "letters", &left_out,
+            )]),
+            style,
+        )));
+    }
 }
 
-fn add_copy_result_spans(los: &mut Vec<Spans>) {
-    los.extend_from_slice(&[
-        Spans::from(Span::raw("")),
-        Spans::from(Span::raw("")),
-        Spans::from(vec![Span::styled(
-            "Press C to copy result to clipboard",
+/// Adds a line saying how many guesses the word took when it was last solved, if it was, and a
+/// blank line after it.
+fn add_previous_attempts_spans(los: &mut Vec<Spans>, app: &App) {
+    if let Some(attempts) = app.previous_attempts {
+        los.push(Spans::from(Span::styled(
+            fill(&app.settings.messages.previous_attempts, &[(
+                "attempts", &attempts,
+            )]),
             Style::default().add_modifier(Modifier::DIM),
-        )]),
-    ]);
+        )));
+        los.push(Spans::from(Span::raw("")));
+    }
 }
 
-fn emoji_from_status(status: LetterStatus) -> &'static str {
-    match status {
-        LetterStatus::Correct => "🟩",
-        LetterStatus::Incorrect => "🟨",
-        LetterStatus::NotInWord => "⬛",
+/// Adds a line saying how many possible answers remained after each guess.
+fn add_analysis_spans(los: &mut Vec<Spans>, app: &App) {
+    los.push(Spans::from(Span::raw("")));
+    for (guess, count) in app.guesses.iter().zip(&app.remaining) {
+        let word: String = guess.iter().map(|s| s.letter).collect();
+        los.push(Spans::from(Span::styled(
+            fill(&app.settings.messages.remaining, &[
+                ("word", &word),
+                ("count", count),
+            ]),
+            Style::default().add_modifier(Modifier::DIM),
+        )));
+    }
+}
+
+/// Adds a line describing each guess's statuses in words.
+fn add_a11y_result_spans(los: &mut Vec<Spans>, app: &App) {
+    los.push(Spans::from(Span::raw("")));
+    for guess in &app.guesses {
+        let word: String = guess.iter().map(|s| s.letter).collect();
+        let statuses: Vec<_> = guess.iter().map(|s| label_from_status(s.status)).collect();
+        los.push(Spans::from(Span::raw(format!(
+            "{word}: {}",
+            statuses.join(", ")
+        ))));
     }
 }
 
-fn get_spots(input: &str, word: &str) -> [Spot; 5] {
-    let mut spots = [Spot::default(); 5];
+/// Returns the lines of the result menu, with the message left by the last selection in place
+/// of the navigation hint.
+fn menu_spans(app: &App) -> Vec<Spans<'_>> {
+    let mut los = vec![Spans::from(Span::raw(""))];
 
-    for (index, letter) in input.chars().enumerate() {
-        if letter == word.as_bytes()[index] as char {
-            spots[index] = Spot::correct(letter);
-        } else if word.contains(|c| c == letter) {
-            spots[index] = Spot::incorrect(letter);
+    for (index, item) in MENU.iter().enumerate() {
+        let style = if index == app.menu_index {
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
         } else {
-            spots[index] = Spot::not_in_word(letter);
+            Style::default()
+        };
+        los.push(Spans::from(Span::styled(
+            format!(" {} ", item.label(&app.settings.messages)),
+            style,
+        )));
+    }
+
+    los.push(Spans::from(Span::raw("")));
+    los.push(match &app.message {
+        Some(message) => Spans::from(Span::styled(message, app.fg(Color::Green))),
+        None => Spans::from(Span::styled(
+            app.settings.messages.menu_hint.as_str(),
+            Style::default().add_modifier(Modifier::DIM),
+        )),
+    });
+
+    if let Some(code) = &app.settings.share_code {
+        los.push(Spans::from(Span::styled(
+            fill(&app.settings.messages.share_code, &[("code", code)]),
+            Style::default().add_modifier(Modifier::DIM),
+        )));
+    }
+
+    los
+}
+
+/// Returns the shareable result: the header, a blank line, then a row of emoji for each guess.
+///
+/// A compact result puts the header and rows on a single line, separated by spaces.
+pub fn share_text(
+    mode: GameMode,
+    index: usize,
+    result: &GuessResult,
+    theme: Theme,
+    emojis: &Emojis,
+    compact: bool,
+) -> String {
+    let mut text = result_header(mode, index, result);
+    text.push_str(if compact { " " } else { "\n\n" });
+    for (i, guess) in result.guesses.iter().enumerate() {
+        if compact && i > 0 {
+            text.push(' ');
+        }
+        for spot in guess {
+            text.push_str(emoji_from_status(spot.status, theme, emojis));
+        }
+        if !compact {
+            text.push('\n');
         }
     }
+    if compact {
+        text.push('\n');
+    }
+    text
+}
+
+/// Returns the result with the letters of each guess, followed by a `G`, `Y` or `-` for the
+/// status of each letter, like `CRANE  G-Y--`.
+pub fn board_text(mode: GameMode, index: usize, result: &GuessResult) -> String {
+    let mut text = result_header(mode, index, result);
+    text.push_str("\n\n");
+    for guess in &result.guesses {
+        text.extend(guess.iter().map(|spot| spot.letter));
+        text.push_str("  ");
+        text.extend(guess.iter().map(|spot| symbol_from_status(spot.status)));
+        text.push('\n');
+    }
+    text
+}
+
+/// Returns the shareable result of the game, which is both shown and copied.
+fn build_share_text(app: &App) -> String {
+    share_text(
+        app.settings.mode,
+        app.index,
+        &app.result(),
+        app.theme,
+        &app.settings.emojis,
+        app.settings.compact_share,
+    )
+}
+
+/// Copies the shareable result to the clipboard.
+fn copy_result(app: &mut App) {
+    let text = build_share_text(app);
+    set_clipboard(app, text);
+}
+
+/// Copies the letters of each guess and their statuses to the clipboard.
+fn copy_board(app: &mut App) {
+    let text = board_text(app.settings.mode, app.index, &app.result());
+    set_clipboard(app, text);
+}
+
+/// Copies the text to the clipboard, leaving a message saying whether it worked.
+///
+/// A machine without a clipboard, like one reached over SSH, only gets an error message, so
+/// the finished game is still saved.
+fn set_clipboard(app: &mut App, text: String) {
+    let copied = Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text));
+    app.message = Some(match copied {
+        Ok(()) => app.settings.messages.copied.clone(),
+        Err(e) => AppError::from(e).to_string(),
+    });
+}
+
+fn emoji_from_status(status: LetterStatus, theme: Theme, emojis: &Emojis) -> &str {
+    if let Some(emoji) = emojis.get(status) {
+        return emoji;
+    }
+
+    match (theme, status) {
+        (Theme::Normal, LetterStatus::Correct) => "🟩",
+        (Theme::Normal, LetterStatus::Incorrect) => "🟨",
+        (Theme::HighContrast, LetterStatus::Correct) => "🟧",
+        (Theme::HighContrast, LetterStatus::Incorrect) => "🟦",
+        (Theme::Monochrome, LetterStatus::Correct) => "⬜",
+        (Theme::Monochrome, LetterStatus::Incorrect) => "🔳",
+        (_, LetterStatus::NotInWord) => "⬛",
+    }
+}
+
+/// Returns the allowed word closest to the input, if one is within two edits of it.
+///
+/// Ties are broken alphabetically.
+fn closest_word<'a>(input: &str, allowed_guesses: &'a HashSet<String>) -> Option<&'a str> {
+    allowed_guesses
+        .iter()
+        .filter_map(|w| edit_distance(input, w, 2).map(|d| (d, w.as_str())))
+        .min()
+        .map(|(_, w)| w)
+}
+
+/// Returns the Levenshtein distance between two words, or [`None`] if it is more than `max`.
+///
+/// Stops early once every path through the current row already exceeds `max`.
+fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        if current.iter().all(|&d| d > max) {
+            return None;
+        }
+        previous = current;
+    }
+
+    Some(previous[b.len()]).filter(|&d| d <= max)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crossterm::event::{Event, KeyCode};
+    use tui::backend::TestBackend;
+
+    use super::*;
+    use crate::events::{key, ScriptedEvents};
+
+    /// A clock that moves a second forward every time it's read, so no key press is ever
+    /// mistaken for a repeat.
+    struct TickingClock(Cell<SystemTime>);
+
+    impl Clock for TickingClock {
+        fn now(&self) -> SystemTime {
+            let now = self.0.get() + Duration::from_secs(1);
+            self.0.set(now);
+            now
+        }
+    }
+
+    fn clock() -> TickingClock {
+        TickingClock(Cell::new(SystemTime::UNIX_EPOCH))
+    }
 
-    spots
+    fn app(word: &str, max_attempts: usize, settings: Settings) -> App {
+        let allowed_guesses = ["CRANE", "SLATE", "ENSUE", "EERIE", "PIOUS", word]
+            .map(String::from)
+            .into();
+        let puzzle = Puzzle {
+            word: word.to_string(),
+            display_word: word.to_string(),
+            allowed_guesses,
+            index: 42,
+            played: 42,
+            total: 2315,
+            max_attempts,
+            previous_attempts: None,
+            candidates: Vec::new(),
+        };
+        App::new(puzzle, settings, Theme::Normal, clock().now())
+    }
+
+    /// Plays the events on an 80x24 terminal and returns what was shown last.
+    fn play(app: &mut App, events: Vec<Event>) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let mut events = ScriptedEvents::new(events);
+        // the last event leaves the game, so draw once more to see the screen it left
+        run_app(&mut terminal, app, &clock(), &mut events).unwrap();
+        terminal
+            .draw(|f| match app.outcome {
+                Some(GameOutcome::Win(attempts)) => success_ui(f, app, attempts),
+                Some(GameOutcome::Loss) => loss_ui(f, app),
+                None => game_ui(f, app),
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let width = buffer.area.width as usize;
+        let symbols: Vec<_> = buffer.content.iter().map(|c| c.symbol.as_str()).collect();
+        symbols
+            .chunks(width)
+            .map(|row| row.concat())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn typing_and_submitting_adds_a_guess() {
+        let mut app = app("SLATE", 6, Settings::default());
+        let mut events = ScriptedEvents::typed("cran");
+        events.pop();
+        events.extend([key(KeyCode::Backspace)]);
+        events.extend(ScriptedEvents::typed("ne"));
+        let screen = play(&mut app, events);
+
+        assert_eq!(app.attempts, 1);
+        assert_eq!(app.guesses[0].map(|s| s.letter), ['C', 'R', 'A', 'N', 'E']);
+        assert!(app.outcome.is_none());
+        assert!(screen.contains("Guesses 1/6"));
+    }
+
+    #[test]
+    fn result_header_marks_only_a_loss_with_x() {
+        let mut unfinished = app("SLATE", 2, Settings::default());
+        play(&mut unfinished, ScriptedEvents::typed("crane"));
+        assert_eq!(
+            result_header(GameMode::Sequential, 42, &unfinished.result()),
+            "Wordle 43 1/2"
+        );
+
+        let mut lost = app("SLATE", 2, Settings::default());
+        let mut events = ScriptedEvents::typed("crane");
+        events.extend(ScriptedEvents::typed("pious"));
+        play(&mut lost, events);
+        assert_eq!(
+            result_header(GameMode::Sequential, 42, &lost.result()),
+            "Wordle 43 X/2"
+        );
+        assert_eq!(
+            result_header(GameMode::TwoPlayer, 42, &lost.result()),
+            "Wordle (two player) X/2"
+        );
+        assert_eq!(
+            result_header(GameMode::Adversarial, 42, &lost.result()),
+            "Wordle (adversarial) X/2"
+        );
+    }
+
+    #[test]
+    fn two_player_games_have_no_puzzle_number() {
+        let settings = Settings {
+            two_player: true,
+            mode: GameMode::TwoPlayer,
+            ..Settings::default()
+        };
+        let mut app = app("", 6, settings);
+        let mut events = ScriptedEvents::typed("slate");
+        events.extend(ScriptedEvents::typed("crane"));
+        let screen = play(&mut app, events);
+
+        assert!(screen.contains(&app.settings.messages.mode_two_player));
+        assert!(!screen.contains("Word 43"));
+        assert!(build_share_text(&app).starts_with("Wordle (two player) 1/6"));
+    }
+
+    #[test]
+    fn adversarial_games_stay_consistent_with_every_reveal() {
+        let settings = Settings {
+            adversarial: true,
+            mode: GameMode::Adversarial,
+            ..Settings::default()
+        };
+        let mut app = app("", 6, settings);
+        app.candidates = ["CRANE", "SLATE", "ENSUE", "EERIE", "PIOUS"]
+            .map(String::from)
+            .into();
+        let mut events = ScriptedEvents::typed("crane");
+        events.extend(ScriptedEvents::typed("eerie"));
+        play(&mut app, events);
+
+        assert!(!app.word.is_empty());
+        assert!(app.guesses.iter().all(|g| is_consistent(&app.word, g)));
+        assert!(build_share_text(&app).starts_with("Wordle (adversarial) 2/6"));
+    }
+
+    #[test]
+    fn footer_counts_the_words_played_rather_than_the_index() {
+        let mut app = app("SLATE", 6, Settings::default());
+        // the first word when playing newest first
+        app.index = 2314;
+        app.played = 0;
+        let screen = play(&mut app, Vec::new());
+
+        assert!(screen.contains("[----------] Word 1 of 2315"));
+    }
+
+    #[test]
+    fn rejects_a_guess_that_is_not_allowed() {
+        let mut app = app("SLATE", 6, Settings::default());
+        play(&mut app, ScriptedEvents::typed("zzzzz"));
+
+        assert_eq!(app.attempts, 0);
+        assert_eq!(
+            app.message,
+            Some(app.settings.messages.invalid_guess.clone())
+        );
+    }
+
+    #[test]
+    fn finding_the_word_wins() {
+        let mut app = app("SLATE", 6, Settings::default());
+        let mut events = ScriptedEvents::typed("crane");
+        events.extend(ScriptedEvents::typed("slate"));
+        play(&mut app, events);
+
+        assert_eq!(app.outcome, Some(GameOutcome::Win(2)));
+        assert!(app.result().is_win());
+    }
+
+    #[test]
+    fn using_every_guess_loses() {
+        let mut app = app("SLATE", 2, Settings::default());
+        let mut events = ScriptedEvents::typed("crane");
+        events.extend(ScriptedEvents::typed("pious"));
+        play(&mut app, events);
+
+        assert_eq!(app.outcome, Some(GameOutcome::Loss));
+        assert!(!app.result().is_win());
+    }
+
+    #[test]
+    fn result_screen_fits_a_six_guess_loss() {
+        let settings = Settings {
+            share_code: Some("0123456789".to_string()),
+            ..Settings::default()
+        };
+        let mut app = app("SLATE", 6, settings);
+        let events = ["crane", "ensue", "eerie", "pious", "crane", "ensue"]
+            .into_iter()
+            .flat_map(ScriptedEvents::typed)
+            .collect();
+        let screen = play(&mut app, events);
+
+        assert_eq!(app.outcome, Some(GameOutcome::Loss));
+        assert!(screen.contains("Wordle 43 X/6"));
+        // every guess is shown beside its row of the shareable result
+        assert_eq!(screen.matches("ENSUE").count(), 2);
+        for item in MENU {
+            assert!(screen.contains(item.label(&app.settings.messages)));
+        }
+        assert!(screen.contains(&app.settings.messages.menu_hint));
+        assert!(screen.contains("0123456789"));
+    }
+
+    #[test]
+    fn result_screen_shows_exactly_what_is_copied() {
+        for compact_share in [false, true] {
+            let settings = Settings {
+                compact_share,
+                ..Settings::default()
+            };
+            let mut app = app("SLATE", 6, settings);
+            let mut events = ScriptedEvents::typed("crane");
+            events.extend(ScriptedEvents::typed("slate"));
+            let screen = play(&mut app, events);
+
+            // wide emoji leave a blank cell after them, so spaces are left out of the comparison
+            let screen = screen.replace(' ', "");
+            let copied = build_share_text(&app);
+            for line in copied.lines() {
+                assert!(screen.contains(&line.replace(' ', "")), "{line} not shown");
+            }
+        }
+    }
+
+    #[test]
+    fn result_screen_cuts_off_rows_that_do_not_fit() {
+        let mut app = app("SLATE", 20, Settings::default());
+        let events = (0..20)
+            .flat_map(|_| ScriptedEvents::typed("crane"))
+            .collect();
+        let screen = play(&mut app, events);
+
+        assert_eq!(app.outcome, Some(GameOutcome::Loss));
+        assert!(screen.contains("Wordle 43 X/20"));
+        assert!(screen.contains("more"));
+        for item in MENU {
+            assert!(screen.contains(item.label(&app.settings.messages)));
+        }
+    }
+
+    #[test]
+    fn found_letters_count_repeats_only_as_often_as_the_word_has_them() {
+        let settings = Settings {
+            assist: true,
+            ..Settings::default()
+        };
+        let mut app = app("ENSUE", 6, settings);
+        let screen = play(&mut app, ScriptedEvents::typed("eerie"));
+
+        assert_eq!(
+            found_letter_counts(&app.guesses),
+            BTreeMap::from([('E', 2)])
+        );
+        assert!(screen.contains("E×2"));
+    }
+
+    #[test]
+    fn palette_runs_commands() {
+        let mut app = app("SLATE", 6, Settings::default());
+        let mut events = vec![key(KeyCode::Char(':'))];
+        events.extend(ScriptedEvents::typed("theme monochrome"));
+        events.push(key(KeyCode::Char(':')));
+        events.extend(ScriptedEvents::typed("nope"));
+        play(&mut app, events);
+
+        assert_eq!(app.theme, Theme::Monochrome);
+        assert!(app.palette.is_none());
+        assert_eq!(
+            app.message,
+            Some(fill(&app.settings.messages.unknown_command, &[(
+                "command", &"nope"
+            )]))
+        );
+    }
+
+    #[test]
+    fn parses_color_names_and_hex_codes() {
+        assert_eq!(parse_color("red"), Some(Color::Red));
+        assert_eq!(parse_color(" Light_Blue "), Some(Color::LightBlue));
+        assert_eq!(parse_color("dark-grey"), Some(Color::DarkGray));
+        assert_eq!(parse_color("#538D4E"), Some(Color::Rgb(0x53, 0x8d, 0x4e)));
+    }
+
+    #[test]
+    fn rejects_unknown_colors() {
+        for spec in ["", "purple", "#538d4", "#538d4e0", "#53xd4e", "538d4e"] {
+            assert_eq!(parse_color(spec), None, "{spec}");
+        }
+    }
+
+    #[test]
+    fn invalid_custom_colors_are_reported_and_left_to_the_theme() {
+        let colors = Colors {
+            correct: Some("#538d4e".to_string()),
+            incorrect: Some("mustard".to_string()),
+            not_in_word: None,
+            border: Some("cyan".to_string()),
+        };
+        let mut invalid = Vec::new();
+        let palette = colors.palette(|name, spec| invalid.push(format!("{name} {spec}")));
+
+        assert_eq!(invalid, ["incorrect mustard"]);
+        assert_eq!(palette.correct, Some(Color::Rgb(0x53, 0x8d, 0x4e)));
+        assert_eq!(palette.incorrect, None);
+        assert_eq!(palette.not_in_word, None);
+        assert_eq!(palette.border, Some(Color::Cyan));
+    }
+
+    #[test]
+    fn giving_up_finishes_the_game() {
+        let mut app = app("SLATE", 6, Settings::default());
+        let mut events = ScriptedEvents::typed("crane");
+        events.push(key(KeyCode::Char(':')));
+        events.extend(ScriptedEvents::typed("give-up"));
+        play(&mut app, events);
+
+        assert_eq!(app.outcome, Some(GameOutcome::Loss));
+        let result = app.result();
+        assert!(result.gave_up);
+        assert!(result.is_finished());
+        assert!(!result.is_win());
+    }
+
+    #[test]
+    fn controls_fit_an_80_column_terminal() {
+        for messages in [Messages::default(), Messages::load("es").unwrap()] {
+            let mut app = app("SLATE", 6, Settings {
+                messages,
+                ..Settings::default()
+            });
+            let screen = play(&mut app, Vec::new());
+
+            let controls = app.settings.messages.controls.replace('*', "");
+            assert!(screen.contains(&controls), "{controls}");
+        }
+    }
+
+    #[test]
+    fn paste_fills_the_input() {
+        let mut app = app("SLATE", 6, Settings::default());
+        play(&mut app, vec![Event::Paste("cr4ane!".to_string())]);
+
+        assert_eq!(app.input, "CRANE");
+    }
 }